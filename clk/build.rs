@@ -1,4 +1,9 @@
 fn main() {
+    // Host builds only compile the library for unit tests, skip the ESP linker scripts
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("none") {
+        return;
+    }
+
     linker_be_nice();
     // Add defmt linker script
     println!("cargo:rustc-link-arg=-Tdefmt.x");
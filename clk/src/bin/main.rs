@@ -7,7 +7,10 @@
 )]
 #![deny(clippy::large_stack_frames)]
 
-use clk::{SevenSegmentConfig, SevenSegmentDisplay};
+use clk::{
+    Clock, DirtyRenderer, Emphasis, Layout, SetTime, SevenSegmentConfig, SevenSegmentDisplay,
+    TimeSource, Transition, TransitionStyle,
+};
 use defmt::println;
 use embedded_graphics::prelude::{RgbColor, Size};
 use embedded_graphics::{
@@ -16,8 +19,13 @@ use embedded_graphics::{
 };
 use embedded_hal_bus::spi::ExclusiveDevice;
 use esp_alloc as _;
-use esp_hal::{clock::CpuClock, delay::Delay, gpio, main, rtc_cntl::Rtc};
-use esp_hal::{spi::master::Config, time::Rate};
+use esp_hal::{
+    clock::CpuClock,
+    delay::Delay,
+    gpio::{self, Input, InputConfig, Pull},
+    main,
+};
+use esp_hal::{spi::master::Config, time::{Instant, Rate}};
 use defmt_rtt as _;
 use mipidsi::{Builder, interface::SpiInterface, models::ST7789};
 use embedded_graphics_framebuf::FrameBuf;
@@ -29,8 +37,26 @@ fn panic(_: &core::panic::PanicInfo) -> ! {
 
 extern crate alloc;
 
+/// 主循环周期（毫秒），按键检测以此为单位
+const TICK_MS: u32 = 50;
+/// 长按时间（1 秒）
+const LONG_PRESS_TICKS: u32 = 1000 / TICK_MS;
+/// 数字变化时的滚动动画帧数（每周期一帧）
+const TRANSITION_FRAMES: u32 = 5;
+
 esp_bootloader_esp_idf::esp_app_desc!();
 
+/// 时钟的单调时间源：系统定时器（由晶振驱动）
+///
+/// RTC 慢时钟没有校准，误差比系统定时器大得多
+struct SystemTimer;
+
+impl TimeSource for SystemTimer {
+    fn now_micros(&self) -> u64 {
+        Instant::now().duration_since_epoch().as_micros()
+    }
+}
+
 #[allow(
     clippy::large_stack_frames,
     reason = "it's not unusual to allocate larger buffers etc. in main"
@@ -44,8 +70,11 @@ fn main() -> ! {
     let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
     let peripherals = esp_hal::init(config);
 
-    // 初始化RTC
-    let _rtc = Rtc::new(peripherals.LPWR);
+    // BOOT 按键（GPIO0，按下为低电平）用来设置时间：
+    // 长按进入设置模式；设置时短按当前位加一，长按切换到下一位，最后一位后退出
+    let button = Input::new(peripherals.GPIO0, InputConfig::default().with_pull(Pull::Up));
+    let mut pressed_ticks: u32 = 0;
+    let mut set_time: Option<SetTime> = None;
 
     // LCD 显示初始化
     let dc = gpio::Output::new(peripherals.GPIO15, gpio::Level::Low, Default::default());
@@ -75,10 +104,8 @@ fn main() -> ! {
     // 清空屏幕
     display.clear(Rgb565::BLACK).unwrap();
 
-    // 时间变量
-    let mut hours: u8 = 23;
-    let mut minutes: u8 = 20;
-    let mut seconds: u8 = 0;
+    // 时间由系统定时器推算，不受渲染耗时影响；上电从 00:00:00 开始，长按 BOOT 键设置
+    let mut clock = Clock::new(SystemTimer, 0, 0, 0);
 
     // 创建7段数码管显示配置
     // digit_size: 数字尺寸, digit_spacing: 数字间距, segment_width: 段宽度
@@ -91,32 +118,108 @@ fn main() -> ! {
     let mut data = [Rgb565::BLACK; 240 * 80];
     let mut fbuf: FrameBuf<Rgb565, &mut [Rgb565; 240 * 80]> = FrameBuf::new(&mut data, fbuf_width, fbuf_height);
 
+    // 颜色
+    let color = Rgb565::GREEN;
+    // 未点亮的段显示为暗淡的绿色（约1/4亮度）
+    let inactive_color = Rgb565::new(0, 4, 0);
+    // 每帧只把变化的数字推送到屏幕
+    let mut renderer = DirtyRenderer::new(ss_display, color, Some(inactive_color));
+    // 设置时间时正在修改的数字
+    let edit_color = Rgb565::YELLOW;
+
+    // 设置时间时闪烁相位从上次按键开始计算（毫秒），按键后立即显示新数字
+    let mut edit_started: u64 = 0;
+    // 上次打印的秒
+    let mut printed_second = None;
+    // 上次绘制的内容和动画帧，没有变化时不重绘
+    let mut drawn: Option<(Layout, Emphasis<Rgb565>, u32)> = None;
+
+    // 数字变化时从旧值滚动到新值
+    let mut shown = clock.now().layout();
+    let mut previous = shown;
+    let mut transition = Transition::new(TransitionStyle::SlideUp, TRANSITION_FRAMES);
+
     loop {
-        // 延时 1 秒
-        delay.delay_millis(1000);
-
-        // 更新时间
-        seconds += 1;
-        if seconds >= 60 {
-            seconds = 0;
-            minutes += 1;
-            if minutes >= 60 {
-                minutes = 0;
-                hours += 1;
-                if hours >= 24 {
-                    hours = 0;
+        delay.delay_millis(TICK_MS);
+        let millis = clock.source().now_micros() / 1000;
+
+        // 按键：松开时判断短按，按住达到长按时间时立即触发长按
+        let mut short_press = false;
+        let mut long_press = false;
+        if button.is_low() {
+            pressed_ticks += 1;
+            long_press = pressed_ticks == LONG_PRESS_TICKS;
+        } else {
+            short_press = pressed_ticks > 0 && pressed_ticks < LONG_PRESS_TICKS;
+            pressed_ticks = 0;
+        }
+
+        match set_time.as_mut() {
+            None if long_press => {
+                let now = clock.now();
+                set_time = Some(SetTime::new(now.hours, now.minutes));
+                edit_started = millis;
+                println!("设置时间");
+            }
+            None => {}
+            Some(editor) => {
+                if short_press {
+                    editor.increment();
+                    edit_started = millis;
                 }
+                if long_press {
+                    if editor.next_digit() {
+                        // 从整分开始走时
+                        clock.set_time(editor.hours(), editor.minutes(), 0);
+                        println!("时间已设置: {:02}:{:02}", editor.hours(), editor.minutes());
+                        set_time = None;
+                    }
+                    edit_started = millis;
+                }
+            }
+        }
+
+        let now = clock.now();
+        if printed_second != Some(now.seconds) {
+            printed_second = Some(now.seconds);
+            // Debug 打印
+            println!("time: {:02}:{:02}:{:02}", now.hours, now.minutes, now.seconds);
+        }
+
+        // 正常显示时冒号随秒闪烁，设置时间时正在修改的数字以高亮色闪烁
+        let (layout, emphasis) = match &set_time {
+            None => {
+                let layout = now.layout();
+                let emphasis = Emphasis::none().blink(layout.colon_cells());
+                (layout, emphasis.with_phase(u64::from(now.millis)))
+            }
+            Some(editor) => {
+                let cells = editor.edited_cells();
+                let emphasis = Emphasis::none().blink(cells).highlight(cells, edit_color);
+                (editor.layout(), emphasis.with_phase(millis.wrapping_sub(edit_started)))
+            }
+        };
+
+        // 走时的数字变化播放动画，设置时间时直接显示
+        if layout != shown {
+            previous = shown;
+            shown = layout;
+            transition.restart();
+            if set_time.is_some() {
+                transition.finish();
             }
         }
 
-        // Debug 打印
-        println!("time: {:02}:{:02}:{:02}", hours, minutes, seconds);
+        // 先画当前帧（动画从第 0 帧即旧数字开始），下个周期再前进一帧
+        let frame = (shown, emphasis, transition.frame);
+        if drawn != Some(frame) {
+            drawn = Some(frame);
 
-        // 使用7段数码管显示时间
-        let color = Rgb565::GREEN;
-        // 未点亮的段显示为暗淡的绿色（约1/4亮度）
-        let inactive_color = Rgb565::new(0, 4, 0);
-        ss_display.draw_time(&mut display, &mut fbuf, hours, minutes, seconds, color, Some(inactive_color)).unwrap();
+            // 使用7段数码管显示时间
+            renderer
+                .draw_transition(&mut display, &mut fbuf, &previous, &shown, &transition, &emphasis)
+                .unwrap();
+        }
+        transition.advance();
     }
 }
-
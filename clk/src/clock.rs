@@ -0,0 +1,179 @@
+//! Wall clock time derived from a monotonic time source
+//!
+//! The `Clock` never counts ticks itself, it remembers which time of day a reading of
+//! the source corresponds to and computes the current time from the elapsed source time.
+//! Time spent rendering or polling buttons therefore can't make the clock drift.
+
+use crate::layout::Layout;
+
+/// Seconds in a day, the clock wraps to 00:00:00 after 23:59:59
+pub const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+const MICROS_PER_SECOND: u64 = 1_000_000;
+const MICROS_PER_DAY: u64 = SECONDS_PER_DAY as u64 * MICROS_PER_SECOND;
+
+/// Monotonic time source, e.g. the system timer or the RTC
+pub trait TimeSource {
+    /// Microseconds since an arbitrary fixed point, never going backwards
+    ///
+    /// The value may wrap around at `u64::MAX`
+    fn now_micros(&self) -> u64;
+}
+
+/// Time of day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Time {
+    /// Hours (0-23)
+    pub hours: u8,
+    /// Minutes (0-59)
+    pub minutes: u8,
+    /// Seconds (0-59)
+    pub seconds: u8,
+    /// Milliseconds into the current second (0-999), e.g. for `Emphasis::with_phase`
+    pub millis: u16,
+}
+
+impl Time {
+    fn from_micros_of_day(micros: u64) -> Self {
+        let seconds = micros / MICROS_PER_SECOND;
+        Self {
+            hours: (seconds / 3600) as u8,
+            minutes: (seconds / 60 % 60) as u8,
+            seconds: (seconds % 60) as u8,
+            millis: (micros % MICROS_PER_SECOND / 1000) as u16,
+        }
+    }
+
+    /// Layout showing the time as `HH:MM:SS`
+    pub fn layout(&self) -> Layout {
+        Layout::HoursMinutesSeconds {
+            hours: self.hours,
+            minutes: self.minutes,
+            seconds: self.seconds,
+        }
+    }
+}
+
+/// Time of day kept by a monotonic time source
+pub struct Clock<S> {
+    source: S,
+    /// Source reading at which the time was set
+    base: u64,
+    /// Time of day (microseconds since midnight) at `base`
+    base_time: u64,
+}
+
+impl<S: TimeSource> Clock<S> {
+    /// Create clock showing the given time now
+    pub fn new(source: S, hours: u8, minutes: u8, seconds: u8) -> Self {
+        let mut clock = Self {
+            source,
+            base: 0,
+            base_time: 0,
+        };
+        clock.set_time(hours, minutes, seconds);
+        clock
+    }
+
+    /// Set the time of day, the current second starts now
+    ///
+    /// Values out of range wrap (hours modulo 24, minutes and seconds modulo 60)
+    pub fn set_time(&mut self, hours: u8, minutes: u8, seconds: u8) {
+        let seconds = u64::from(hours % 24) * 3600 + u64::from(minutes % 60) * 60 + u64::from(seconds % 60);
+        self.base = self.source.now_micros();
+        self.base_time = seconds * MICROS_PER_SECOND;
+    }
+
+    /// Current time of day
+    pub fn now(&self) -> Time {
+        let elapsed = self.source.now_micros().wrapping_sub(self.base);
+        Time::from_micros_of_day((self.base_time + elapsed % MICROS_PER_DAY) % MICROS_PER_DAY)
+    }
+
+    /// The time source
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// 测试用时间源，手动推进
+    struct FakeSource(Cell<u64>);
+
+    impl FakeSource {
+        fn advance(&self, micros: u64) {
+            self.0.set(self.0.get().wrapping_add(micros));
+        }
+    }
+
+    impl TimeSource for &FakeSource {
+        fn now_micros(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    fn hms(time: Time) -> (u8, u8, u8) {
+        (time.hours, time.minutes, time.seconds)
+    }
+
+    #[test]
+    fn rolls_over_at_midnight() {
+        let source = FakeSource(Cell::new(123_456_789));
+        let clock = Clock::new(&source, 23, 59, 58);
+        assert_eq!(hms(clock.now()), (23, 59, 58));
+
+        source.advance(1_999_999);
+        assert_eq!(hms(clock.now()), (23, 59, 59));
+        assert_eq!(clock.now().millis, 999);
+        source.advance(1);
+        assert_eq!(clock.now(), Time { hours: 0, minutes: 0, seconds: 0, millis: 0 });
+        source.advance(61 * MICROS_PER_SECOND + 500_000);
+        assert_eq!(clock.now(), Time { hours: 0, minutes: 1, seconds: 1, millis: 500 });
+    }
+
+    #[test]
+    fn uneven_reads_do_not_drift() {
+        let source = FakeSource(Cell::new(0));
+        let clock = Clock::new(&source, 12, 0, 0);
+
+        // 每次读取间隔不是整秒（模拟渲染耗时），跑满两天后仍然准确
+        let step = 1_037_123;
+        let steps = 2 * MICROS_PER_DAY / step;
+        for _ in 0..steps {
+            source.advance(step);
+            let time = clock.now();
+            assert!(time.hours < 24 && time.minutes < 60 && time.seconds < 60 && time.millis < 1000);
+        }
+        source.advance(2 * MICROS_PER_DAY - steps * step);
+        assert_eq!(clock.now(), Time { hours: 12, minutes: 0, seconds: 0, millis: 0 });
+    }
+
+    #[test]
+    fn set_time_starts_from_now() {
+        let source = FakeSource(Cell::new(5 * MICROS_PER_SECOND));
+        let mut clock = Clock::new(&source, 8, 30, 0);
+        source.advance(10 * MICROS_PER_SECOND + 300_000);
+        assert_eq!(hms(clock.now()), (8, 30, 10));
+
+        clock.set_time(23, 59, 59);
+        assert_eq!(clock.now().millis, 0);
+        source.advance(MICROS_PER_SECOND);
+        assert_eq!(hms(clock.now()), (0, 0, 0));
+        // 超出范围的值取模
+        clock.set_time(25, 61, 60);
+        assert_eq!(hms(clock.now()), (1, 1, 0));
+    }
+
+    #[test]
+    fn source_wrap_around_is_seamless() {
+        let source = FakeSource(Cell::new(u64::MAX - MICROS_PER_SECOND / 2));
+        let clock = Clock::new(&source, 23, 59, 59);
+        source.advance(MICROS_PER_SECOND);
+        assert_eq!(hms(clock.now()), (0, 0, 0));
+        assert_eq!(clock.now().layout(), Layout::HoursMinutesSeconds { hours: 0, minutes: 0, seconds: 0 });
+    }
+}
@@ -0,0 +1,97 @@
+//! Blinking and highlighting of individual cells
+//!
+//! Cells are numbered like the cells of `SevenSegmentDisplay::draw_str`: one per
+//! character, colons included, a `.` lighting a decimal point takes no cell.
+//! `Layout::colon_cells` and `Layout::digit_cell` give the masks for a layout.
+
+/// Blink period, blinking cells are shown in the first half and hidden in the second
+pub const BLINK_PERIOD_MS: u64 = 1000;
+
+/// Cells that blink or are drawn in a highlight color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Emphasis<C> {
+    /// Blinking cells, bit n is the n-th cell
+    pub blink: u32,
+    /// Highlighted cells, bit n is the n-th cell
+    pub highlight: u32,
+    /// Color of highlighted cells
+    pub highlight_color: Option<C>,
+    /// Blink phase, blinking cells are hidden while false
+    pub visible: bool,
+}
+
+impl<C: Copy> Emphasis<C> {
+    /// No blinking or highlighted cells
+    pub const fn none() -> Self {
+        Self {
+            blink: 0,
+            highlight: 0,
+            highlight_color: None,
+            visible: true,
+        }
+    }
+
+    /// Add blinking cells
+    pub fn blink(mut self, cells: u32) -> Self {
+        self.blink |= cells;
+        self
+    }
+
+    /// Add highlighted cells, drawn with `color` instead of the normal color
+    pub fn highlight(mut self, cells: u32, color: C) -> Self {
+        self.highlight |= cells;
+        self.highlight_color = Some(color);
+        self
+    }
+
+    /// Set the blink phase from a millisecond clock (half a second on, half a second off)
+    pub fn with_phase(mut self, millis: u64) -> Self {
+        self.visible = millis % BLINK_PERIOD_MS < BLINK_PERIOD_MS / 2;
+        self
+    }
+
+    /// Color of the `index`-th cell, None while a blinking cell is hidden
+    pub fn cell_color(&self, index: usize, color: C) -> Option<C> {
+        let bit = 1_u32.checked_shl(index as u32).unwrap_or(0);
+        if self.blink & bit != 0 && !self.visible {
+            return None;
+        }
+        match self.highlight_color {
+            Some(highlight) if self.highlight & bit != 0 => Some(highlight),
+            _ => Some(color),
+        }
+    }
+}
+
+impl<C: Copy> Default for Emphasis<C> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blinking_cells_follow_half_second_phase() {
+        let emphasis = Emphasis::none().blink(0b10);
+        assert_eq!(emphasis.with_phase(0).cell_color(1, 7), Some(7));
+        assert_eq!(emphasis.with_phase(499).cell_color(1, 7), Some(7));
+        assert_eq!(emphasis.with_phase(500).cell_color(1, 7), None);
+        assert_eq!(emphasis.with_phase(1999).cell_color(1, 7), None);
+        assert_eq!(emphasis.with_phase(2000).cell_color(1, 7), Some(7));
+        // 其他格不闪烁
+        assert_eq!(emphasis.with_phase(500).cell_color(0, 7), Some(7));
+    }
+
+    #[test]
+    fn highlighted_cells_use_highlight_color() {
+        let emphasis = Emphasis::none().highlight(0b100, 9).blink(0b100).with_phase(0);
+        assert_eq!(emphasis.cell_color(2, 7), Some(9));
+        assert_eq!(emphasis.cell_color(3, 7), Some(7));
+        assert_eq!(emphasis.with_phase(600).cell_color(2, 7), None);
+        // 超出掩码范围的格保持原样
+        assert_eq!(emphasis.cell_color(40, 7), Some(7));
+    }
+}
@@ -0,0 +1,240 @@
+//! Display layouts for the 7 segment display
+//!
+//! A `Layout` describes what to show (time in several formats, integers, fixed-point
+//! numbers) and is turned into the text drawn by `SevenSegmentDisplay::draw_str`:
+//! one cell per digit, `:` for colons and `.` for decimal points.
+//!
+//! Suppressed leading zeros are drawn as blank cells, so the width of a layout only
+//! depends on its format and never on the value, and centered layouts don't move.
+
+use crate::seven_segment::cells;
+
+/// Maximum number of cells of a number layout (sign and the 10 digits of an `i32`)
+pub const MAX_NUMBER_DIGITS: u8 = 11;
+
+/// What to show on the display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// `HH:MM:SS` (24 hour)
+    HoursMinutesSeconds { hours: u8, minutes: u8, seconds: u8 },
+    /// `HH:MM` (24 hour)
+    HoursMinutes { hours: u8, minutes: u8 },
+    /// `MM:SS` (timer)
+    MinutesSeconds { minutes: u8, seconds: u8 },
+    /// `hh:MM` 12 hour clock followed by an `A`/`P` indicator cell
+    ///
+    /// `hours` is 0-23, the leading zero of the hour is blank (` 9:05P`, `12:00A`)
+    TwelveHour { hours: u8, minutes: u8 },
+    /// Signed integer right aligned in `digits` cells
+    ///
+    /// Leading zeros are blank, the minus sign takes the cell before the first digit.
+    /// Values that don't fit are shown as a row of `-`.
+    Integer { value: i32, digits: u8 },
+    /// Fixed-point number `value / 10^decimals` right aligned in `digits` cells
+    ///
+    /// The decimal point lights on the last integer digit and takes no cell, at least
+    /// one integer digit is shown (`0.05`). Same sign and overflow rules as `Integer`.
+    Fixed { value: i32, digits: u8, decimals: u8 },
+}
+
+impl Layout {
+    /// Text to draw with `SevenSegmentDisplay::draw_str`
+    pub fn text(&self) -> LayoutText {
+        let mut text = LayoutText::new();
+        match *self {
+            Layout::HoursMinutesSeconds { hours, minutes, seconds } => {
+                text.push_two_digits(hours);
+                text.push(b':');
+                text.push_two_digits(minutes);
+                text.push(b':');
+                text.push_two_digits(seconds);
+            }
+            Layout::HoursMinutes { hours, minutes } => {
+                text.push_two_digits(hours);
+                text.push(b':');
+                text.push_two_digits(minutes);
+            }
+            Layout::MinutesSeconds { minutes, seconds } => {
+                text.push_two_digits(minutes);
+                text.push(b':');
+                text.push_two_digits(seconds);
+            }
+            Layout::TwelveHour { hours, minutes } => {
+                let hours = hours % 24;
+                let twelve = match hours {
+                    0 => 12,
+                    13.. => hours - 12,
+                    _ => hours,
+                };
+                text.push(if twelve >= 10 { b'1' } else { b' ' });
+                text.push(b'0' + twelve % 10);
+                text.push(b':');
+                text.push_two_digits(minutes);
+                text.push(if hours < 12 { b'A' } else { b'P' });
+            }
+            Layout::Integer { value, digits } => text.push_number(value, digits, 0),
+            Layout::Fixed { value, digits, decimals } => text.push_number(value, digits, decimals),
+        }
+        text
+    }
+
+    /// Mask of the colon cells, e.g. to blink them with `Emphasis`
+    pub fn colon_cells(&self) -> u32 {
+        cell_mask(self.text().as_str(), |c| c == ':')
+    }
+
+    /// Mask of the `n`-th digit cell (colons are skipped, blank digits count)
+    pub fn digit_cell(&self, n: usize) -> u32 {
+        let mut digit = 0;
+        cell_mask(self.text().as_str(), |c| {
+            if c == ':' {
+                return false;
+            }
+            digit += 1;
+            digit - 1 == n
+        })
+    }
+}
+
+/// Mask of the cells of `text` matching `pred`
+fn cell_mask(text: &str, mut pred: impl FnMut(char) -> bool) -> u32 {
+    cells(text)
+        .enumerate()
+        .filter(|&(i, (c, _))| i < 32 && pred(c))
+        .fold(0, |mask, (i, _)| mask | 1 << i)
+}
+
+/// Text of a layout, at most 16 ASCII characters stored inline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutText {
+    buf: [u8; 16],
+    len: usize,
+}
+
+impl LayoutText {
+    fn new() -> Self {
+        Self { buf: [0; 16], len: 0 }
+    }
+
+    /// Text as string slice
+    pub fn as_str(&self) -> &str {
+        // Only ASCII is pushed
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+
+    fn push(&mut self, c: u8) {
+        if self.len < self.buf.len() {
+            self.buf[self.len] = c;
+            self.len += 1;
+        }
+    }
+
+    /// Two digits with leading zero (values above 99 keep the last two digits)
+    fn push_two_digits(&mut self, value: u8) {
+        self.push(b'0' + value / 10 % 10);
+        self.push(b'0' + value % 10);
+    }
+
+    fn push_number(&mut self, value: i32, digits: u8, decimals: u8) {
+        let digits = digits.clamp(1, MAX_NUMBER_DIGITS);
+        let decimals = decimals.min(digits - 1);
+        let magnitude = value.unsigned_abs();
+
+        // Significant digits, at least one integer digit in front of the decimals
+        let mut significant = 1;
+        let mut rest = magnitude / 10;
+        while rest > 0 {
+            significant += 1;
+            rest /= 10;
+        }
+        let significant = significant.max(decimals + 1);
+        let sign = u8::from(value < 0);
+
+        if significant + sign > digits {
+            for _ in 0..digits {
+                self.push(b'-');
+            }
+            return;
+        }
+
+        for _ in 0..digits - significant - sign {
+            self.push(b' ');
+        }
+        if value < 0 {
+            self.push(b'-');
+        }
+        let mut divisor = 10_u32.pow(significant as u32 - 1);
+        for i in 0..significant {
+            self.push(b'0' + (magnitude / divisor % 10) as u8);
+            if decimals > 0 && i == significant - decimals - 1 {
+                self.push(b'.');
+            }
+            divisor /= 10;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(layout: Layout) -> std::string::String {
+        layout.text().as_str().into()
+    }
+
+    #[test]
+    fn time_layouts() {
+        assert_eq!(text(Layout::HoursMinutesSeconds { hours: 7, minutes: 5, seconds: 9 }), "07:05:09");
+        assert_eq!(text(Layout::HoursMinutes { hours: 23, minutes: 59 }), "23:59");
+        assert_eq!(text(Layout::MinutesSeconds { minutes: 0, seconds: 30 }), "00:30");
+    }
+
+    #[test]
+    fn twelve_hour_clock() {
+        assert_eq!(text(Layout::TwelveHour { hours: 0, minutes: 0 }), "12:00A");
+        assert_eq!(text(Layout::TwelveHour { hours: 9, minutes: 5 }), " 9:05A");
+        assert_eq!(text(Layout::TwelveHour { hours: 12, minutes: 30 }), "12:30P");
+        assert_eq!(text(Layout::TwelveHour { hours: 13, minutes: 0 }), " 1:00P");
+        assert_eq!(text(Layout::TwelveHour { hours: 23, minutes: 59 }), "11:59P");
+    }
+
+    #[test]
+    fn integers_suppress_leading_zeros() {
+        assert_eq!(text(Layout::Integer { value: 42, digits: 4 }), "  42");
+        assert_eq!(text(Layout::Integer { value: 0, digits: 4 }), "   0");
+        assert_eq!(text(Layout::Integer { value: -42, digits: 4 }), " -42");
+        assert_eq!(text(Layout::Integer { value: -999, digits: 4 }), "-999");
+        assert_eq!(text(Layout::Integer { value: i32::MIN, digits: MAX_NUMBER_DIGITS }), "-2147483648");
+    }
+
+    #[test]
+    fn numbers_that_do_not_fit_show_dashes() {
+        assert_eq!(text(Layout::Integer { value: 10000, digits: 4 }), "----");
+        assert_eq!(text(Layout::Integer { value: -1000, digits: 4 }), "----");
+        assert_eq!(text(Layout::Fixed { value: 1000, digits: 3, decimals: 1 }), "---");
+    }
+
+    #[test]
+    fn cell_masks() {
+        let hms = Layout::HoursMinutesSeconds { hours: 12, minutes: 34, seconds: 56 };
+        assert_eq!(hms.colon_cells(), 0b00100100);
+        assert_eq!(hms.digit_cell(0), 0b00000001);
+        assert_eq!(hms.digit_cell(2), 0b00001000);
+        assert_eq!(hms.digit_cell(5), 0b10000000);
+        assert_eq!(hms.digit_cell(6), 0);
+        // 小数点不占格
+        let fixed = Layout::Fixed { value: 1234, digits: 4, decimals: 2 };
+        assert_eq!(fixed.colon_cells(), 0);
+        assert_eq!(fixed.digit_cell(2), 0b0100);
+    }
+
+    #[test]
+    fn fixed_point_numbers() {
+        assert_eq!(text(Layout::Fixed { value: 1234, digits: 4, decimals: 2 }), "12.34");
+        assert_eq!(text(Layout::Fixed { value: 5, digits: 4, decimals: 2 }), " 0.05");
+        assert_eq!(text(Layout::Fixed { value: -5, digits: 4, decimals: 2 }), "-0.05");
+        assert_eq!(text(Layout::Fixed { value: 215, digits: 4, decimals: 1 }), " 21.5");
+        // 小数位数不能占满所有格
+        assert_eq!(text(Layout::Fixed { value: 7, digits: 2, decimals: 5 }), "0.7");
+    }
+}
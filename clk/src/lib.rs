@@ -1,9 +1,31 @@
 //! 时钟项目库
 //! 
-//! 包含7段数码管显示模块
+//! 包含7段（以及14/16段）数码管显示模块、时间/数字的显示布局、闪烁/高亮效果、
+//! 设置时间模式、只重绘变化数字的渲染器，以及由单调时间源驱动的时钟
+//!
+//! 绘制代码不依赖硬件，可以在主机上运行单元测试：
+//!
+//! ```text
+//! cargo +stable test --lib --target x86_64-unknown-linux-gnu
+//! ```
 
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
+pub mod clock;
+pub mod emphasis;
+pub mod layout;
+pub mod renderer;
+pub mod set_time;
 pub mod seven_segment;
+pub mod transition;
 
-pub use seven_segment::{Segments, SevenSegmentConfig, SevenSegmentDisplay};
+pub use clock::{Clock, Time, TimeSource};
+pub use emphasis::Emphasis;
+pub use layout::Layout;
+pub use renderer::DirtyRenderer;
+pub use set_time::SetTime;
+pub use seven_segment::{
+    AlphaSegmentDisplay, AlphaSegments, FourteenSegmentDisplay, SegmentStyle, Segments,
+    SevenSegmentConfig, SevenSegmentDisplay, SixteenSegmentDisplay,
+};
+pub use transition::{Transition, TransitionStyle};
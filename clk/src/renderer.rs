@@ -0,0 +1,274 @@
+//! Redraw only the changed digits
+//!
+//! `DirtyRenderer` remembers what each cell of the last frame showed. Every frame is
+//! still drawn into the frame buffer (cheap), but only the areas of the cells that
+//! changed are pushed to the display, so a clock ticking seconds sends one digit over
+//! SPI instead of the whole frame buffer.
+
+use embedded_graphics::{
+    pixelcolor::{PixelColor, Rgb888},
+    prelude::*,
+    primitives::Rectangle,
+};
+use embedded_graphics_framebuf::FrameBuf;
+
+use crate::emphasis::Emphasis;
+use crate::layout::Layout;
+use crate::seven_segment::{cells, SevenSegmentDisplay};
+use crate::transition::{Transition, TransitionStyle};
+
+/// Maximum number of cells tracked (length of a layout text)
+const MAX_CELLS: usize = 16;
+
+/// What one cell showed
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CellState<C> {
+    area: Rectangle,
+    c: char,
+    dp: bool,
+    /// None while a blinking cell is hidden
+    color: Option<C>,
+    /// Old character, decimal point and frame while the cell is animated
+    transition: Option<(char, bool, u32)>,
+}
+
+/// Stateful layout renderer pushing only changed cells to the display
+pub struct DirtyRenderer<C> {
+    ss: SevenSegmentDisplay,
+    color: C,
+    inactive_color: Option<C>,
+    cells: [Option<CellState<C>>; MAX_CELLS],
+    /// Position and width of the last layout in the frame buffer, None forces a full redraw
+    placement: Option<(Point, u32)>,
+}
+
+impl<C> DirtyRenderer<C>
+where
+    C: PixelColor + Default + Into<Rgb888> + From<Rgb888>,
+{
+    /// Create renderer, the first frame is pushed completely
+    ///
+    /// inactive_color: color for inactive segments (dim effect), None to hide completely
+    pub fn new(ss: SevenSegmentDisplay, color: C, inactive_color: Option<C>) -> Self {
+        Self {
+            ss,
+            color,
+            inactive_color,
+            cells: [None; MAX_CELLS],
+            placement: None,
+        }
+    }
+
+    /// Push the whole frame buffer on the next draw (e.g. after the display was cleared)
+    pub fn invalidate(&mut self) {
+        self.placement = None;
+    }
+
+    /// Draw a layout, see `SevenSegmentDisplay::draw_layout_emphasized`
+    pub fn draw<T, const N: usize>(
+        &mut self,
+        display: &mut T,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        layout: &Layout,
+        emphasis: &Emphasis<C>,
+    ) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+    {
+        let finished = Transition::new(TransitionStyle::Fade, 0);
+        self.draw_transition(display, fbuf, layout, layout, &finished, emphasis)
+    }
+
+    /// Draw a frame of a layout transition, see `SevenSegmentDisplay::draw_layout_transition`
+    pub fn draw_transition<T, const N: usize>(
+        &mut self,
+        display: &mut T,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        from: &Layout,
+        to: &Layout,
+        transition: &Transition,
+        emphasis: &Emphasis<C>,
+    ) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+    {
+        // Clear frame buffer and draw the whole frame
+        for pixel in fbuf.data.iter_mut() {
+            *pixel = C::default();
+        }
+        let _ = self.ss.draw_layout_transition_to(
+            fbuf,
+            from,
+            to,
+            transition,
+            self.color,
+            self.inactive_color,
+            emphasis,
+        );
+
+        // Cell states, matching what draw_layout_transition_to drew
+        let (from_text, to_text) = (from.text(), to.text());
+        let (from_text, to_text) = (from_text.as_str(), to_text.as_str());
+        let animated = !transition.is_finished() && cells(from_text).count() == cells(to_text).count();
+        let position = self.ss.layout_position(fbuf.bounding_box(), to_text);
+        let mut old_cells = cells(from_text);
+        let mut states = [None; MAX_CELLS];
+        for (i, ((c, dp), area)) in cells(to_text).zip(self.ss.cell_areas(to_text, position)).enumerate() {
+            let color = emphasis.cell_color(i, self.color);
+            let old = old_cells.next().unwrap_or((c, dp));
+            let changing = animated && c != ':' && color.is_some() && old != (c, dp);
+            if let Some(state) = states.get_mut(i) {
+                *state = Some(CellState {
+                    area,
+                    c,
+                    dp,
+                    color,
+                    transition: changing.then_some((old.0, old.1, transition.frame)),
+                });
+            }
+        }
+
+        // Layout moved or changed shape (or too many cells to track): push everything
+        let placement = (position, self.ss.str_width(to_text));
+        let tracked = cells(to_text).count() <= MAX_CELLS;
+        if self.placement != Some(placement) || !tracked {
+            self.ss.push_fbuf(display, fbuf)?;
+            self.placement = tracked.then_some(placement);
+            self.cells = states;
+            return Ok(());
+        }
+
+        for (old, new) in self.cells.iter_mut().zip(states) {
+            if *old != new {
+                if let Some(state) = new {
+                    self.ss.push_fbuf_area(display, fbuf, &state.area)?;
+                }
+                *old = new;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seven_segment::SevenSegmentConfig;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    /// Draw target counting the pixels written to it
+    struct Counting<'a, D> {
+        target: &'a mut D,
+        pixels: usize,
+    }
+
+    impl<D: DrawTarget> Dimensions for Counting<'_, D> {
+        fn bounding_box(&self) -> Rectangle {
+            self.target.bounding_box()
+        }
+    }
+
+    impl<D: DrawTarget> DrawTarget for Counting<'_, D> {
+        type Color = D::Color;
+        type Error = D::Error;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let count = &mut self.pixels;
+            self.target.draw_iter(pixels.into_iter().inspect(|_| *count += 1))
+        }
+    }
+
+    const FBUF_W: usize = 62;
+    const FBUF_H: usize = 16;
+
+    fn display() -> SevenSegmentDisplay {
+        SevenSegmentDisplay::new(SevenSegmentConfig::new(Size::new(6, 11), 1, 2))
+    }
+
+    fn hms(seconds: u8) -> Layout {
+        Layout::HoursMinutesSeconds { hours: 12, minutes: 34, seconds }
+    }
+
+    /// 画一帧，返回推送到屏幕的像素数
+    fn draw(
+        renderer: &mut DirtyRenderer<BinaryColor>,
+        target: &mut MockDisplay<BinaryColor>,
+        from: &Layout,
+        to: &Layout,
+        transition: &Transition,
+    ) -> usize {
+        let mut data = [BinaryColor::Off; FBUF_W * FBUF_H];
+        let mut fbuf = FrameBuf::new(&mut data, FBUF_W, FBUF_H);
+        let mut counting = Counting { target, pixels: 0 };
+        renderer
+            .draw_transition(&mut counting, &mut fbuf, from, to, transition, &Emphasis::none())
+            .unwrap();
+        counting.pixels
+    }
+
+    fn full_redraw(layout: &Layout) -> MockDisplay<BinaryColor> {
+        let mut target = MockDisplay::new();
+        let mut data = [BinaryColor::Off; FBUF_W * FBUF_H];
+        let mut fbuf = FrameBuf::new(&mut data, FBUF_W, FBUF_H);
+        display().draw_layout(&mut target, &mut fbuf, layout, BinaryColor::On, None).unwrap();
+        target
+    }
+
+    #[test]
+    fn only_changed_digit_is_pushed() {
+        let mut renderer = DirtyRenderer::new(display(), BinaryColor::On, None);
+        let mut target = MockDisplay::new();
+        target.set_allow_overdraw(true);
+        let finished = Transition::new(TransitionStyle::Fade, 0);
+
+        // 第一帧推送整个帧缓冲区
+        assert_eq!(draw(&mut renderer, &mut target, &hms(56), &hms(56), &finished), FBUF_W * FBUF_H);
+        // 下一秒只推送秒的个位（数字宽度 + 间距，数字高度）
+        assert_eq!(draw(&mut renderer, &mut target, &hms(56), &hms(57), &finished), (6 + 1) * 11);
+        // 没有变化时什么都不推送
+        assert_eq!(draw(&mut renderer, &mut target, &hms(57), &hms(57), &finished), 0);
+        // 59 -> 00 两位都变
+        assert_eq!(draw(&mut renderer, &mut target, &hms(59), &hms(0), &finished), 2 * (6 + 1) * 11);
+
+        // 屏幕内容和完整重绘一致
+        assert_eq!(target, full_redraw(&hms(0)));
+    }
+
+    #[test]
+    fn transition_frames_push_animated_cell() {
+        let mut renderer = DirtyRenderer::new(display(), BinaryColor::On, None);
+        let mut target = MockDisplay::new();
+        target.set_allow_overdraw(true);
+        let mut transition = Transition::new(TransitionStyle::SlideUp, 2);
+
+        draw(&mut renderer, &mut target, &hms(56), &hms(56), &transition);
+        transition.restart();
+        // 每一帧（包括结束帧）都只推送动画中的数字
+        for _ in 0..3 {
+            assert_eq!(draw(&mut renderer, &mut target, &hms(56), &hms(57), &transition), (6 + 1) * 11);
+            transition.advance();
+        }
+        assert_eq!(draw(&mut renderer, &mut target, &hms(56), &hms(57), &transition), 0);
+        assert_eq!(target, full_redraw(&hms(57)));
+    }
+
+    #[test]
+    fn changed_layout_is_pushed_completely() {
+        let mut renderer = DirtyRenderer::new(display(), BinaryColor::On, None);
+        let mut target = MockDisplay::new();
+        target.set_allow_overdraw(true);
+        let finished = Transition::new(TransitionStyle::Fade, 0);
+        let hm = Layout::HoursMinutes { hours: 12, minutes: 34 };
+
+        draw(&mut renderer, &mut target, &hms(56), &hms(56), &finished);
+        assert_eq!(draw(&mut renderer, &mut target, &hms(56), &hm, &finished), FBUF_W * FBUF_H);
+        assert_eq!(target, full_redraw(&hm));
+
+        // invalidate 之后重新推送全部
+        renderer.invalidate();
+        assert_eq!(draw(&mut renderer, &mut target, &hm, &hm, &finished), FBUF_W * FBUF_H);
+    }
+}
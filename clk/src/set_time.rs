@@ -0,0 +1,140 @@
+//! Set-time mode: edit the clock one digit at a time
+//!
+//! The digits of `HH:MM` are edited from left to right, `increment` steps the edited
+//! digit and wraps within the valid range (hours 00-23, minutes 00-59).
+
+use crate::layout::Layout;
+
+/// Number of editable digits (HH:MM)
+pub const EDIT_DIGITS: usize = 4;
+
+/// Time being edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetTime {
+    hours: u8,
+    minutes: u8,
+    /// Edited digit, 0-3 from the left of HH:MM
+    digit: usize,
+}
+
+impl SetTime {
+    /// Start editing at the hours tens digit
+    pub fn new(hours: u8, minutes: u8) -> Self {
+        Self {
+            hours: hours % 24,
+            minutes: minutes % 60,
+            digit: 0,
+        }
+    }
+
+    /// Hours (0-23)
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    /// Minutes (0-59)
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    /// Edited digit, 0-3 from the left of HH:MM
+    pub fn digit(&self) -> usize {
+        self.digit
+    }
+
+    /// Step the edited digit, wrapping to 0 after its largest valid value
+    pub fn increment(&mut self) {
+        let (tens, units) = (self.hours / 10, self.hours % 10);
+        match self.digit {
+            0 => {
+                let tens = (tens + 1) % 3;
+                // 2x only goes up to 23
+                let units = if tens == 2 { units.min(3) } else { units };
+                self.hours = tens * 10 + units;
+            }
+            1 => {
+                let max = if tens == 2 { 3 } else { 9 };
+                self.hours = tens * 10 + (units + 1) % (max + 1);
+            }
+            2 => self.minutes = (self.minutes / 10 + 1) % 6 * 10 + self.minutes % 10,
+            _ => self.minutes = self.minutes / 10 * 10 + (self.minutes % 10 + 1) % 10,
+        }
+    }
+
+    /// Move to the next digit, returns true when all digits are done
+    pub fn next_digit(&mut self) -> bool {
+        self.digit += 1;
+        self.digit >= EDIT_DIGITS
+    }
+
+    /// Layout showing the edited time, seconds are shown as 00
+    pub fn layout(&self) -> Layout {
+        Layout::HoursMinutesSeconds {
+            hours: self.hours,
+            minutes: self.minutes,
+            seconds: 0,
+        }
+    }
+
+    /// Cell mask of the edited digit in `layout`, e.g. to blink it with `Emphasis`
+    pub fn edited_cells(&self) -> u32 {
+        self.layout().digit_cell(self.digit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(hours: u8, minutes: u8, digit: usize, times: usize) -> (u8, u8) {
+        let mut set = SetTime::new(hours, minutes);
+        for _ in 0..digit {
+            set.next_digit();
+        }
+        for _ in 0..times {
+            set.increment();
+        }
+        (set.hours(), set.minutes())
+    }
+
+    #[test]
+    fn hours_stay_below_24() {
+        assert_eq!(edit(9, 0, 0, 1), (19, 0));
+        // 19 的十位加到 2 时个位收到 3
+        assert_eq!(edit(19, 0, 0, 1), (23, 0));
+        assert_eq!(edit(23, 0, 0, 1), (3, 0));
+        assert_eq!(edit(23, 0, 1, 1), (20, 0));
+        assert_eq!(edit(19, 0, 1, 1), (10, 0));
+        assert_eq!(edit(5, 0, 1, 3), (8, 0));
+    }
+
+    #[test]
+    fn minutes_stay_below_60() {
+        assert_eq!(edit(0, 59, 2, 1), (0, 9));
+        assert_eq!(edit(0, 45, 2, 1), (0, 55));
+        assert_eq!(edit(0, 59, 3, 1), (0, 50));
+        assert_eq!(edit(0, 30, 3, 10), (0, 30));
+    }
+
+    #[test]
+    fn every_increment_is_a_valid_time() {
+        for digit in 0..EDIT_DIGITS {
+            for times in 0..30 {
+                let (hours, minutes) = edit(17, 42, digit, times);
+                assert!(hours < 24 && minutes < 60, "{hours}:{minutes}");
+            }
+        }
+    }
+
+    #[test]
+    fn edited_cells_skip_colon() {
+        let mut set = SetTime::new(12, 34);
+        assert_eq!(set.edited_cells(), 0b0001);
+        assert!(!set.next_digit());
+        assert_eq!(set.edited_cells(), 0b0010);
+        assert!(!set.next_digit());
+        assert_eq!(set.edited_cells(), 0b1000);
+        assert!(!set.next_digit());
+        assert!(set.next_digit());
+    }
+}
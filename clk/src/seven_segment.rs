@@ -1,7 +1,8 @@
 //! 7 segment digital clock display module
 //! 
 //! Using embedded_graphics to draw classic 7-segment digital clock
-//! Segment style is hexagon (pointed ends, 90 degrees)
+//! Segment style is hexagon (pointed ends, 90 degrees) by default, see `SegmentStyle`
+//! for skewed, rounded and plain segments
 
 use embedded_graphics::{
     prelude::*,
@@ -11,9 +12,15 @@ use embedded_graphics::{
 };
 use embedded_graphics_framebuf::FrameBuf;
 
+use embedded_graphics::pixelcolor::Rgb888;
+
+use crate::emphasis::Emphasis;
+use crate::layout::Layout;
+use crate::transition::{blend, Transition, TransitionStyle};
+
 /// 7 segment bit definitions
 /// 
-/// Segment layout:
+/// Segment layout (DP is drawn in the spacing after the digit):
 /// ```text
 ///    AAAAA
 ///   F     B
@@ -23,8 +30,9 @@ use embedded_graphics_framebuf::FrameBuf;
 ///   E     C
 ///   E     C
 ///   E     C
-///    DDDDD
+///    DDDDD  DP
 /// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Segments(pub u8);
 
 impl Segments {
@@ -42,6 +50,8 @@ impl Segments {
     pub const F: u8 = 0b00000010;
     /// Segment G (middle horizontal)
     pub const G: u8 = 0b00000001;
+    /// Decimal point
+    pub const DP: u8 = 0b10000000;
 
     /// Create empty segments
     pub const fn empty() -> Self {
@@ -66,6 +76,61 @@ impl Segments {
         Some(Self(bits))
     }
 
+    /// Create segments from character
+    /// 
+    /// Supports digits, hex letters A-F and the letters that are readable on
+    /// 7 segments (A b C c d E F G H h I J L n O o P q r S t U u y), plus
+    /// `-`, `_`, `=`, `°` and space. Letters with only one form match either case
+    /// (`'a'` draws `A`, `'B'` draws `b`). Returns None for other characters.
+    pub fn from_char(c: char) -> Option<Self> {
+        if let Some(digit) = c.to_digit(10) {
+            return Self::from_digit(digit as u8);
+        }
+
+        let bits = match c {
+            'A' => Self::A | Self::B | Self::C | Self::E | Self::F | Self::G,
+            'b' => Self::C | Self::D | Self::E | Self::F | Self::G,
+            'C' => Self::A | Self::D | Self::E | Self::F,
+            'c' => Self::D | Self::E | Self::G,
+            'd' => Self::B | Self::C | Self::D | Self::E | Self::G,
+            'E' => Self::A | Self::D | Self::E | Self::F | Self::G,
+            'F' => Self::A | Self::E | Self::F | Self::G,
+            'G' => Self::A | Self::C | Self::D | Self::E | Self::F,
+            'H' => Self::B | Self::C | Self::E | Self::F | Self::G,
+            'h' => Self::C | Self::E | Self::F | Self::G,
+            'I' => Self::E | Self::F,
+            'J' => Self::B | Self::C | Self::D | Self::E,
+            'L' => Self::D | Self::E | Self::F,
+            'n' => Self::C | Self::E | Self::G,
+            'O' => Self::A | Self::B | Self::C | Self::D | Self::E | Self::F,
+            'o' => Self::C | Self::D | Self::E | Self::G,
+            'P' => Self::A | Self::B | Self::E | Self::F | Self::G,
+            'q' => Self::A | Self::B | Self::C | Self::F | Self::G,
+            'r' => Self::E | Self::G,
+            'S' => Self::A | Self::C | Self::D | Self::F | Self::G,
+            't' => Self::D | Self::E | Self::F | Self::G,
+            'U' => Self::B | Self::C | Self::D | Self::E | Self::F,
+            'u' => Self::C | Self::D | Self::E,
+            'y' => Self::B | Self::C | Self::D | Self::F | Self::G,
+            '-' => Self::G,
+            '_' => Self::D,
+            '=' => Self::D | Self::G,
+            '°' => Self::A | Self::B | Self::F | Self::G,
+            ' ' => 0,
+            // Letter with only one form: try the other case
+            c if c.is_ascii_lowercase() => return Self::from_char(c.to_ascii_uppercase()),
+            c if c.is_ascii_uppercase() => {
+                let lower = c.to_ascii_lowercase();
+                return match lower {
+                    'b' | 'd' | 'n' | 'q' | 'r' | 't' | 'y' => Self::from_char(lower),
+                    _ => None,
+                };
+            }
+            _ => return None,
+        };
+        Some(Self(bits))
+    }
+
     /// Check if contains segment
     pub fn contains(&self, segment: u8) -> bool {
         (self.0 & segment) != 0
@@ -86,6 +151,26 @@ impl core::ops::BitOr<u8> for Segments {
     }
 }
 
+/// Segment drawing style
+/// 
+/// Rounded and plain ends don't interlock at the corners like the pointed ones,
+/// so their horizontal segments are shortened to keep a gap to the vertical segments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SegmentStyle {
+    /// Hexagon with pointed ends (90 degrees)
+    #[default]
+    Hexagon,
+    /// Hexagon segments slanted like a classic LCD (italic)
+    /// 
+    /// The value is the horizontal shift of the top row relative to the bottom row,
+    /// rows in between are shifted proportionally
+    Skewed(u32),
+    /// Bar with rounded (semicircular) ends
+    Rounded,
+    /// Plain rectangle
+    Plain,
+}
+
 /// 7 segment display configuration
 #[derive(Debug, Clone, Copy)]
 pub struct SevenSegmentConfig {
@@ -95,6 +180,8 @@ pub struct SevenSegmentConfig {
     pub digit_spacing: u32,
     /// Segment width (thickness)
     pub segment_width: u32,
+    /// Segment drawing style
+    pub segment_style: SegmentStyle,
 }
 
 impl Default for SevenSegmentConfig {
@@ -103,340 +190,1973 @@ impl Default for SevenSegmentConfig {
             digit_size: Size::new(24, 48),
             digit_spacing: 8,
             segment_width: 4,
+            segment_style: SegmentStyle::Hexagon,
         }
     }
 }
 
 impl SevenSegmentConfig {
-    /// Create new configuration
+    /// Create new configuration (hexagon segments)
     pub fn new(digit_size: Size, digit_spacing: u32, segment_width: u32) -> Self {
         Self {
             digit_size,
             digit_spacing,
             segment_width,
+            segment_style: SegmentStyle::Hexagon,
+        }
+    }
+
+    /// Set segment drawing style
+    pub fn with_style(mut self, segment_style: SegmentStyle) -> Self {
+        self.segment_style = segment_style;
+        self
+    }
+
+    /// Extra width on the right of a digit cell taken by the slant of skewed segments
+    fn slant(&self) -> u32 {
+        match self.segment_style {
+            SegmentStyle::Skewed(slant) => slant,
+            _ => 0,
         }
     }
 }
 
-/// 7 segment display drawer
-pub struct SevenSegmentDisplay {
-    config: SevenSegmentConfig,
+/// Distance the end of a segment is cut back on one scanline
+/// 
+/// `distance_2x` is twice the distance of the scanline from the segment center line,
+/// `thickness` is the segment width
+fn end_inset(style: SegmentStyle, thickness: u32, distance_2x: i32) -> i32 {
+    match style {
+        SegmentStyle::Hexagon | SegmentStyle::Skewed(_) => distance_2x / 2,
+        // Circle of diameter `thickness`, measured at pixel centers
+        SegmentStyle::Rounded => {
+            let t = thickness as i32;
+            (t - (t * t - distance_2x * distance_2x).isqrt()) / 2
+        }
+        SegmentStyle::Plain => 0,
+    }
 }
 
-impl SevenSegmentDisplay {
-    /// Create new 7 segment display
-    pub fn new(config: SevenSegmentConfig) -> Self {
-        Self { config }
+/// Draw horizontal segment (hexagon with pointed ends by default)
+/// 
+/// Draw by scanning each row, width decreases based on distance from center line
+fn draw_horizontal_segment<D, C>(target: &mut D, rect: Rectangle, style: SegmentStyle, color: C) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    if rect.is_zero_sized() {
+        return Ok(());
     }
 
-    /// Draw horizontal segment (hexagon with pointed ends)
-    /// 
-    /// Draw by scanning each row, width decreases based on distance from center line
-    fn draw_horizontal_segment<C, const N: usize>(
-        &self,
-        fbuf: &mut FrameBuf<C, &mut [C; N]>,
-        rect: Rectangle,
-        color: C,
-    ) where
-        C: PixelColor + Default,
-    {
-        if rect.is_zero_sized() {
-            return;
+    let center_2x = rect.top_left * 2 + (rect.size - Size::new(1, 1));
+
+    for y in rect.rows() {
+        let offset = end_inset(style, rect.size.height, (y * 2 - center_2x.y).abs());
+
+        // Narrow segments can be shorter than both insets together
+        let width = rect.size.width.saturating_sub(offset as u32 * 2);
+        if width == 0 {
+            continue;
         }
+        let scanline = Rectangle::new(Point::new(rect.top_left.x + offset, y), Size::new(width, 1));
+
+        scanline.into_styled(PrimitiveStyle::with_fill(color)).draw(target)?;
+    }
+
+    Ok(())
+}
 
-        let center_2x = rect.top_left * 2 + (rect.size - Size::new(1, 1));
+/// Draw vertical segment (hexagon with pointed ends by default)
+/// 
+/// Draw by scanning each column, height decreases based on distance from center line
+fn draw_vertical_segment<D, C>(target: &mut D, rect: Rectangle, style: SegmentStyle, color: C) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    if rect.is_zero_sized() {
+        return Ok(());
+    }
 
-        for y in rect.rows() {
-            let offset = (y * 2 - center_2x.y).abs() / 2;
+    let center_2x = rect.top_left * 2 + (rect.size - Size::new(1, 1));
 
-            let scanline = Rectangle::new(
-                Point::new(rect.top_left.x + offset, y),
-                Size::new(rect.size.width - offset as u32 * 2, 1),
-            );
+    for x in rect.columns() {
+        let offset = end_inset(style, rect.size.width, (x * 2 - center_2x.x).abs());
 
-            let _ = scanline.into_styled(PrimitiveStyle::with_fill(color)).draw(fbuf);
+        let height = rect.size.height.saturating_sub(offset as u32 * 2);
+        if height == 0 {
+            continue;
         }
+        let scanline = Rectangle::new(Point::new(x, rect.top_left.y + offset), Size::new(1, height));
+
+        scanline.into_styled(PrimitiveStyle::with_fill(color)).draw(target)?;
     }
 
-    /// Draw vertical segment (hexagon with pointed ends)
-    /// 
-    /// Draw by scanning each column, height decreases based on distance from center line
-    fn draw_vertical_segment<C, const N: usize>(
-        &self,
-        fbuf: &mut FrameBuf<C, &mut [C; N]>,
-        rect: Rectangle,
-        color: C,
-    ) where
-        C: PixelColor + Default,
-    {
-        if rect.is_zero_sized() {
-            return;
+    Ok(())
+}
+
+/// Draw segment (auto select horizontal or vertical based on rectangle orientation)
+fn draw_segment<D, C>(target: &mut D, mut rect: Rectangle, style: SegmentStyle, color: C) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    if rect.size.width > rect.size.height {
+        if matches!(style, SegmentStyle::Rounded | SegmentStyle::Plain) {
+            // Without pointed ends the corners would overlap, keep the horizontal
+            // segment clear of the vertical ones
+            let inset = rect.size.height - rect.size.height / 2;
+            rect.top_left.x += inset as i32;
+            rect.size.width = rect.size.width.saturating_sub(2 * inset);
         }
+        draw_horizontal_segment(target, rect, style, color)
+    } else {
+        draw_vertical_segment(target, rect, style, color)
+    }
+}
 
-        let center_2x = rect.top_left * 2 + (rect.size - Size::new(1, 1));
+/// Draw target adapter that slants a digit cell for `SegmentStyle::Skewed`
+/// 
+/// Whole rows are shifted to the right, more the higher they are in the cell, so every
+/// shape keeps its pixels and rows stay contiguous. A slant of 0 draws unchanged.
+struct Skew<'a, D> {
+    target: &'a mut D,
+    shear: Shear,
+}
 
-        for x in rect.columns() {
-            let offset = (x * 2 - center_2x.x).abs() / 2;
+/// Row shift of a skewed digit cell
+#[derive(Clone, Copy)]
+struct Shear {
+    /// Bottom row of the digit cell (not shifted)
+    bottom: i32,
+    /// Digit height minus one, the row distance over which the shift reaches `slant`
+    span: i32,
+    slant: i32,
+}
 
-            let scanline = Rectangle::new(
-                Point::new(x, rect.top_left.y + offset),
-                Size::new(1, rect.size.height - offset as u32 * 2),
-            );
+impl Shear {
+    fn shift(self, y: i32) -> i32 {
+        (self.slant * (self.bottom - y) + self.span / 2).div_euclid(self.span)
+    }
+}
 
-            let _ = scanline.into_styled(PrimitiveStyle::with_fill(color)).draw(fbuf);
-        }
+impl<'a, D: DrawTarget> Skew<'a, D> {
+    fn new(config: &SevenSegmentConfig, target: &'a mut D, position: Point) -> Self {
+        let span = config.digit_size.height.saturating_sub(1).max(1) as i32;
+        let shear = Shear {
+            bottom: position.y + span,
+            span,
+            slant: config.slant() as i32,
+        };
+        Self { target, shear }
     }
+}
 
-    /// Draw segment (auto select horizontal or vertical based on rectangle orientation)
-    fn draw_segment<C, const N: usize>(
-        &self,
-        fbuf: &mut FrameBuf<C, &mut [C; N]>,
-        rect: Rectangle,
-        color: C,
-    ) where
-        C: PixelColor + Default,
+impl<D: DrawTarget> Dimensions for Skew<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Skew<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        if rect.size.width > rect.size.height {
-            self.draw_horizontal_segment(fbuf, rect, color);
-        } else {
-            self.draw_vertical_segment(fbuf, rect, color);
+        let shear = self.shear;
+        if shear.slant == 0 {
+            return self.target.draw_iter(pixels);
         }
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(p, c)| Pixel(Point::new(p.x + shear.shift(p.y), p.y), c)),
+        )
     }
 
-    /// Create reduced size segment rectangle (avoid overlap between horizontal and vertical segments)
-    fn reduced_rect(&self, mut rect: Rectangle) -> Rectangle {
-        if rect.is_zero_sized() {
-            return rect;
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let shear = self.shear;
+        if shear.slant == 0 {
+            return self.target.fill_solid(area, color);
+        }
+        for y in area.rows() {
+            let row = Rectangle::new(Point::new(area.top_left.x + shear.shift(y), y), Size::new(area.size.width, 1));
+            self.target.fill_solid(&row, color)?;
         }
+        Ok(())
+    }
+}
+
+/// Create reduced size segment rectangle (avoid overlap between horizontal and vertical segments)
+fn reduced_rect(mut rect: Rectangle) -> Rectangle {
+    if rect.is_zero_sized() {
+        return rect;
+    }
+
+    if rect.size.width > rect.size.height {
+        // Horizontal segment: reduce width
+        let size_offset = rect.size.height / 2 + 1;
+        rect.top_left.x += size_offset as i32;
+        rect.size.width = rect.size.width.saturating_sub(2 * size_offset);
+    } else {
+        // Vertical segment: reduce height
+        let size_offset = rect.size.width / 2 + 1;
+        rect.top_left.y += size_offset as i32;
+        rect.size.height = rect.size.height.saturating_sub(2 * size_offset);
+    }
+
+    rect
+}
+
+/// Draw diagonal segment (parallelogram from one corner of `rect` to the opposite one)
+/// 
+/// Draw by scanning each row, a run of `thickness` pixels moves across the rectangle;
+/// `rising` goes from bottom-left to top-right
+fn draw_diagonal_segment<D, C>(
+    target: &mut D,
+    rect: Rectangle,
+    thickness: u32,
+    rising: bool,
+    color: C,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    if rect.is_zero_sized() {
+        return Ok(());
+    }
+
+    let run = thickness.min(rect.size.width);
+    let travel = (rect.size.width - run) as i32;
+    let last_row = (rect.size.height - 1).max(1) as i32;
+
+    for (i, y) in rect.rows().enumerate() {
+        let step = (i as i32 * travel + last_row / 2) / last_row;
+        let offset = if rising { travel - step } else { step };
+
+        let scanline = Rectangle::new(Point::new(rect.top_left.x + offset, y), Size::new(run, 1));
+        scanline.into_styled(PrimitiveStyle::with_fill(color)).draw(target)?;
+    }
+
+    Ok(())
+}
+
+/// Draw colon (two square dots at 1/3 and 2/3 of the digit height)
+fn draw_colon<D, C>(config: &SevenSegmentConfig, target: &mut D, position: Point, color: C) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    let target = &mut Skew::new(config, target, position);
+    let sw = config.segment_width;
+    let h = config.digit_size.height;
+    let dy = h / 3;
+
+    // Top dot
+    let rect1 = Rectangle::new(
+        position + Size::new(0, dy - sw / 2),
+        Size::new(sw, sw),
+    );
+    rect1.into_styled(PrimitiveStyle::with_fill(color)).draw(target)?;
 
-        if rect.size.width > rect.size.height {
-            // Horizontal segment: reduce width
-            let size_offset = rect.size.height / 2 + 1;
-            rect.top_left.x += size_offset as i32;
-            rect.size.width = rect.size.width.saturating_sub(2 * size_offset);
-        } else {
-            // Vertical segment: reduce height
-            let size_offset = rect.size.width / 2 + 1;
-            rect.top_left.y += size_offset as i32;
-            rect.size.height = rect.size.height.saturating_sub(2 * size_offset);
+    // Bottom dot
+    let rect2 = Rectangle::new(
+        position + Size::new(0, dy * 2 - sw / 2),
+        Size::new(sw, sw),
+    );
+    rect2.into_styled(PrimitiveStyle::with_fill(color)).draw(target)
+}
+
+/// Draw decimal point (square dot at the bottom, centered in the spacing after the digit)
+fn draw_decimal_point<D, C>(
+    config: &SevenSegmentConfig,
+    target: &mut D,
+    position: Point,
+    color: C,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    let sw = config.segment_width;
+    let x = config.digit_size.width as i32 + (config.digit_spacing as i32 - sw as i32) / 2;
+    let y = config.digit_size.height as i32 - sw as i32;
+
+    let rect = Rectangle::new(position + Point::new(x, y), Size::new(sw, sw));
+    rect.into_styled(PrimitiveStyle::with_fill(color)).draw(target)
+}
+
+/// Split text into character cells, `.` attaches a decimal point to the previous character
+/// 
+/// A `.` at the start, after a colon or after another `.` takes a blank cell of its own
+pub(crate) fn cells(text: &str) -> impl Iterator<Item = (char, bool)> + '_ {
+    let mut chars = text.chars().peekable();
+    core::iter::from_fn(move || {
+        let c = chars.next()?;
+        if c == '.' {
+            return Some((' ', true));
         }
+        let dp = c != ':' && chars.next_if_eq(&'.').is_some();
+        Some((c, dp))
+    })
+}
+
+/// Display position of a frame buffer pushed to the center of the display
+fn fbuf_origin<T, C, const N: usize>(display: &T, fbuf: &FrameBuf<C, &mut [C; N]>) -> Point
+where
+    T: DrawTarget<Color = C>,
+    C: PixelColor + Default,
+{
+    let fbuf_size = fbuf.size();
+    let display_center = display.bounding_box().center();
+    Point::new(
+        display_center.x - (fbuf_size.width as i32 / 2),
+        display_center.y - (fbuf_size.height as i32 / 2),
+    )
+}
 
-        rect
+/// Segments of one character cell of a string
+fn cell_segments(c: char, dp: bool) -> Segments {
+    let segments = Segments::from_char(c).unwrap_or(Segments::empty());
+    if dp {
+        segments | Segments::DP
+    } else {
+        segments
     }
+}
 
-    /// Draw single digit to frame buffer
-    pub fn draw_digit_to_fbuf<C, const N: usize>(
+/// Width of one character cell (colons are narrower than digits)
+fn cell_width(config: &SevenSegmentConfig, c: char) -> u32 {
+    if c == ':' {
+        config.segment_width
+    } else {
+        config.digit_size.width
+    }
+}
+
+/// Width of a string drawn with `draw_str` (including the slant of skewed segments)
+fn text_width(config: &SevenSegmentConfig, text: &str) -> u32 {
+    let mut count = 0;
+    let mut width = 0;
+    for (c, _) in cells(text) {
+        count += 1;
+        width += cell_width(config, c);
+    }
+    if count == 0 {
+        return 0;
+    }
+    width + config.digit_spacing * (count - 1) + config.slant()
+}
+
+/// 7 segment display drawer
+pub struct SevenSegmentDisplay {
+    config: SevenSegmentConfig,
+}
+
+impl SevenSegmentDisplay {
+    /// Create new 7 segment display
+    pub fn new(config: SevenSegmentConfig) -> Self {
+        Self { config }
+    }
+
+    /// Draw single digit to any draw target
+    pub fn draw_digit<D, C>(
         &self,
-        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        target: &mut D,
         digit: u8,
         position: Point,
         color: C,
         inactive_color: Option<C>,
-    ) where
-        C: PixelColor + Default,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
     {
         let segments = Segments::from_digit(digit).unwrap_or(Segments::empty());
-        self.draw_segments_to_fbuf(fbuf, &segments, position, color, inactive_color);
+        self.draw_segments(target, &segments, position, color, inactive_color)
     }
 
-    /// Draw segments to frame buffer
+    /// Draw segments to any draw target
     /// 
     /// Key: horizontal and vertical segments share the same starting coordinates
     /// Overlap is avoided by reducing size during drawing
     /// 
     /// inactive_color: color for inactive segments (dim effect), None to hide completely
-    pub fn draw_segments_to_fbuf<C, const N: usize>(
+    pub fn draw_segments<D, C>(
         &self,
-        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        target: &mut D,
         segments: &Segments,
         position: Point,
         color: C,
         inactive_color: Option<C>,
-    ) where
-        C: PixelColor + Default,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
     {
         let cfg = &self.config;
         let sw = cfg.segment_width;
         let w = cfg.digit_size.width;
         let h = cfg.digit_size.height;
+        let target = &mut Skew::new(cfg, target, position);
 
         // Segment rectangles (full size, will be reduced during drawing)
         // Horizontal segment width = digit width
         // Vertical segment height = digit height / 2
+        let rects = [
+            // Segment A (top horizontal)
+            (Segments::A, Rectangle::new(position, Size::new(w, sw))),
+            // Segment F (top left vertical)
+            (Segments::F, Rectangle::new(position, Size::new(sw, h / 2))),
+            // Segment B (top right vertical)
+            (Segments::B, Rectangle::new(position + Size::new(w - sw, 0), Size::new(sw, h / 2))),
+            // Segment G (middle horizontal)
+            (Segments::G, Rectangle::new(position + Size::new(0, h / 2 - sw / 2), Size::new(w, sw))),
+            // Segment E (bottom left vertical)
+            (Segments::E, Rectangle::new(position + Size::new(0, h / 2), Size::new(sw, h / 2))),
+            // Segment C (bottom right vertical)
+            (Segments::C, Rectangle::new(position + Size::new(w - sw, h / 2), Size::new(sw, h / 2))),
+            // Segment D (bottom horizontal)
+            (Segments::D, Rectangle::new(position + Size::new(0, h - sw), Size::new(w, sw))),
+        ];
 
-        // Segment A (top horizontal)
-        let rect_a = Rectangle::new(position, Size::new(w, sw));
-        if segments.contains(Segments::A) {
-            self.draw_segment(fbuf, self.reduced_rect(rect_a), color);
-        } else if let Some(inactive) = inactive_color {
-            self.draw_segment(fbuf, self.reduced_rect(rect_a), inactive);
+        for (segment, rect) in rects {
+            if segments.contains(segment) {
+                draw_segment(target, reduced_rect(rect), cfg.segment_style, color)?;
+            } else if let Some(inactive) = inactive_color {
+                draw_segment(target, reduced_rect(rect), cfg.segment_style, inactive)?;
+            }
         }
 
-        // Segment F (top left vertical)
-        let rect_f = Rectangle::new(position, Size::new(sw, h / 2));
-        if segments.contains(Segments::F) {
-            self.draw_segment(fbuf, self.reduced_rect(rect_f), color);
-        } else if let Some(inactive) = inactive_color {
-            self.draw_segment(fbuf, self.reduced_rect(rect_f), inactive);
+        // The decimal point lies outside the digit cell, only draw it when lit
+        if segments.contains(Segments::DP) {
+            draw_decimal_point(cfg, target, position, color)?;
         }
 
-        // Segment B (top right vertical)
-        let rect_b = Rectangle::new(position + Size::new(w - sw, 0), Size::new(sw, h / 2));
-        if segments.contains(Segments::B) {
-            self.draw_segment(fbuf, self.reduced_rect(rect_b), color);
-        } else if let Some(inactive) = inactive_color {
-            self.draw_segment(fbuf, self.reduced_rect(rect_b), inactive);
-        }
+        Ok(())
+    }
 
-        // Segment G (middle horizontal)
-        let rect_g = Rectangle::new(position + Size::new(0, h / 2 - sw / 2), Size::new(w, sw));
-        if segments.contains(Segments::G) {
-            self.draw_segment(fbuf, self.reduced_rect(rect_g), color);
-        } else if let Some(inactive) = inactive_color {
-            self.draw_segment(fbuf, self.reduced_rect(rect_g), inactive);
-        }
+    /// Draw colon to any draw target
+    pub fn draw_colon<D, C>(&self, target: &mut D, position: Point, color: C) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        draw_colon(&self.config, target, position, color)
+    }
 
-        // Segment E (bottom left vertical)
-        let rect_e = Rectangle::new(position + Size::new(0, h / 2), Size::new(sw, h / 2));
-        if segments.contains(Segments::E) {
-            self.draw_segment(fbuf, self.reduced_rect(rect_e), color);
-        } else if let Some(inactive) = inactive_color {
-            self.draw_segment(fbuf, self.reduced_rect(rect_e), inactive);
-        }
+    /// Draw a string, one digit cell per character
+    /// 
+    /// Characters are mapped with `Segments::from_char`, `:` draws a colon, `.` lights
+    /// the decimal point of the previous character and unsupported characters are
+    /// drawn as blank digits. Returns the position after the last character.
+    /// 
+    /// inactive_color: color for inactive segments (dim effect), None to hide completely
+    pub fn draw_str<D, C>(
+        &self,
+        target: &mut D,
+        text: &str,
+        position: Point,
+        color: C,
+        inactive_color: Option<C>,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        self.draw_str_emphasized(target, text, position, color, inactive_color, &Emphasis::none())
+    }
 
-        // Segment C (bottom right vertical)
-        let rect_c = Rectangle::new(position + Size::new(w - sw, h / 2), Size::new(sw, h / 2));
-        if segments.contains(Segments::C) {
-            self.draw_segment(fbuf, self.reduced_rect(rect_c), color);
-        } else if let Some(inactive) = inactive_color {
-            self.draw_segment(fbuf, self.reduced_rect(rect_c), inactive);
+    /// Draw a string like `draw_str`, with blinking and highlighted cells
+    /// 
+    /// A hidden blinking cell is drawn like a blank digit (all segments, colon included,
+    /// in inactive_color), so it keeps its place on the display
+    pub fn draw_str_emphasized<D, C>(
+        &self,
+        target: &mut D,
+        text: &str,
+        position: Point,
+        color: C,
+        inactive_color: Option<C>,
+        emphasis: &Emphasis<C>,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        let mut position = position;
+        for (i, (c, dp)) in cells(text).enumerate() {
+            self.draw_cell(target, c, dp, position, emphasis.cell_color(i, color), inactive_color)?;
+            position.x += cell_width(&self.config, c) as i32 + self.config.digit_spacing as i32;
         }
+        Ok(position)
+    }
 
-        // Segment D (bottom horizontal)
-        let rect_d = Rectangle::new(position + Size::new(0, h - sw), Size::new(w, sw));
-        if segments.contains(Segments::D) {
-            self.draw_segment(fbuf, self.reduced_rect(rect_d), color);
-        } else if let Some(inactive) = inactive_color {
-            self.draw_segment(fbuf, self.reduced_rect(rect_d), inactive);
+    /// Draw one cell of a string, `color` None draws a hidden (blinking) cell
+    fn draw_cell<D, C>(
+        &self,
+        target: &mut D,
+        c: char,
+        dp: bool,
+        position: Point,
+        color: Option<C>,
+        inactive_color: Option<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        if c == ':' {
+            if let Some(colon_color) = color.or(inactive_color) {
+                self.draw_colon(target, position, colon_color)?;
+            }
+            return Ok(());
+        }
+        match color {
+            Some(color) => self.draw_segments(target, &cell_segments(c, dp), position, color, inactive_color),
+            None => match inactive_color {
+                Some(inactive) => self.draw_segments(target, &Segments::empty(), position, inactive, Some(inactive)),
+                None => Ok(()),
+            },
         }
     }
 
-    /// Draw colon to frame buffer
-    pub fn draw_colon_to_fbuf<C, const N: usize>(
+    /// Draw the frame `transition.frame` of a digit changing from `from` to `to`
+    /// 
+    /// Fade interpolates the segments turning on or off between inactive_color and color
+    /// (black when inactive_color is None). Slides move both digits vertically, clipped
+    /// to the cell; inactive segments stay in place.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_segments_transition<D, C>(
         &self,
-        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        target: &mut D,
+        from: &Segments,
+        to: &Segments,
         position: Point,
+        transition: &Transition,
         color: C,
-    ) where
-        C: PixelColor + Default,
+        inactive_color: Option<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor + Into<Rgb888> + From<Rgb888>,
     {
-        let cfg = &self.config;
-        let sw = cfg.segment_width;
-        let h = cfg.digit_size.height;
-        let dy = h / 3;
+        if transition.is_finished() || from == to {
+            return self.draw_segments(target, to, position, color, inactive_color);
+        }
+        let (frame, frames) = (transition.frame, transition.frames);
 
-        // Top dot
-        let rect1 = Rectangle::new(
-            position + Size::new(0, dy - sw / 2),
-            Size::new(sw, sw),
-        );
-        let _ = rect1.into_styled(PrimitiveStyle::with_fill(color)).draw(fbuf);
+        match transition.style {
+            TransitionStyle::Fade => {
+                let base = inactive_color.unwrap_or_else(|| C::from(Rgb888::new(0, 0, 0)));
+                // The decimal point is never drawn inactive
+                if let Some(inactive) = inactive_color {
+                    let off = !(from.0 | to.0) & !Segments::DP;
+                    self.draw_segments(target, &Segments(off), position, inactive, None)?;
+                }
+                let groups = [
+                    (from.0 & to.0, color),
+                    (!from.0 & to.0, blend(base, color, frame, frames)),
+                    (from.0 & !to.0, blend(color, base, frame, frames)),
+                ];
+                for (bits, group_color) in groups {
+                    self.draw_segments(target, &Segments(bits), position, group_color, None)?;
+                }
+            }
+            TransitionStyle::SlideUp | TransitionStyle::SlideDown => {
+                if let Some(inactive) = inactive_color {
+                    self.draw_segments(target, &Segments::empty(), position, inactive, Some(inactive))?;
+                }
+                let cfg = &self.config;
+                let h = cfg.digit_size.height as i32;
+                let offset = h * frame as i32 / frames as i32;
+                let (old_dy, new_dy) = if transition.style == TransitionStyle::SlideUp {
+                    (-offset, h - offset)
+                } else {
+                    (offset, offset - h)
+                };
 
-        // Bottom dot
-        let rect2 = Rectangle::new(
-            position + Size::new(0, dy * 2 - sw / 2),
-            Size::new(sw, sw),
-        );
-        let _ = rect2.into_styled(PrimitiveStyle::with_fill(color)).draw(fbuf);
+                // The cell includes the decimal point and the slant of skewed segments
+                let cell = Rectangle::new(
+                    position,
+                    Size::new(cfg.digit_size.width + cfg.digit_spacing + cfg.slant(), cfg.digit_size.height),
+                );
+                let mut clipped = target.clipped(&cell);
+                self.draw_segments(&mut clipped, from, position + Point::new(0, old_dy), color, None)?;
+                self.draw_segments(&mut clipped, to, position + Point::new(0, new_dy), color, None)?;
+            }
+        }
+        Ok(())
     }
 
-    /// Draw time to frame buffer and display (format: HH:MM:SS)
+    /// Calculate width of a string drawn with `draw_str`
+    pub fn str_width(&self, text: &str) -> u32 {
+        text_width(&self.config, text)
+    }
+
+    /// Calculate width of a layout drawn with `draw_layout_to`
+    pub fn layout_width(&self, layout: &Layout) -> u32 {
+        self.str_width(layout.text().as_str())
+    }
+
+    /// Draw a layout centered in the target's bounding box
+    /// 
+    /// Only the segments, colons and decimal points are drawn, the background is left
+    /// untouched, so the target can be the display itself, a `cropped()` sub-region or any buffer
     /// 
     /// inactive_color: color for inactive segments (dim effect), None to hide completely
-    pub fn draw_time<T, C, const N: usize>(
+    pub fn draw_layout_to<D, C>(
         &self,
-        display: &mut T,
-        fbuf: &mut FrameBuf<C, &mut [C; N]>,
-        hours: u8,
-        minutes: u8,
-        seconds: u8,
+        target: &mut D,
+        layout: &Layout,
         color: C,
         inactive_color: Option<C>,
-    ) -> Result<(), T::Error>
+    ) -> Result<(), D::Error>
     where
-        T: DrawTarget<Color = C>,
-        C: PixelColor + Default,
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
     {
-        let cfg = &self.config;
-        let digit_width = cfg.digit_size.width;
-        let digit_spacing = cfg.digit_spacing;
-        let segment_width = cfg.segment_width;
-        
-        // Clear frame buffer
-        for pixel in fbuf.data.iter_mut() {
-            *pixel = C::default();
-        }
-
-        // Calculate starting position (centered)
-        let fbuf_size = fbuf.size();
-        let total_width = Self::time_display_width(cfg);
-        let start_x = (fbuf_size.width.saturating_sub(total_width)) as i32 / 2;
-        let start_y = (fbuf_size.height.saturating_sub(cfg.digit_size.height)) as i32 / 2;
-
-        let mut position = Point::new(start_x, start_y);
+        self.draw_layout_emphasized_to(target, layout, color, inactive_color, &Emphasis::none())
+    }
 
-        // Draw hours tens
-        self.draw_digit_to_fbuf(fbuf, hours / 10, position, color, inactive_color);
-        position.x += digit_width as i32 + digit_spacing as i32;
+    /// Draw a layout centered in the target's bounding box, with blinking and highlighted cells
+    /// 
+    /// See `draw_layout_to` and `draw_str_emphasized`
+    pub fn draw_layout_emphasized_to<D, C>(
+        &self,
+        target: &mut D,
+        layout: &Layout,
+        color: C,
+        inactive_color: Option<C>,
+        emphasis: &Emphasis<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        let text = layout.text();
 
-        // Draw hours units
-        self.draw_digit_to_fbuf(fbuf, hours % 10, position, color, inactive_color);
-        position.x += digit_width as i32 + digit_spacing as i32;
+        let position = self.layout_position(target.bounding_box(), text.as_str());
+        self.draw_str_emphasized(target, text.as_str(), position, color, inactive_color, emphasis)?;
 
-        // Draw first colon
-        self.draw_colon_to_fbuf(fbuf, position, color);
-        position.x += segment_width as i32 + digit_spacing as i32;
+        Ok(())
+    }
 
-        // Draw minutes tens
-        self.draw_digit_to_fbuf(fbuf, minutes / 10, position, color, inactive_color);
-        position.x += digit_width as i32 + digit_spacing as i32;
+    /// Draw the frame `transition.frame` of a layout changing from `from` to `to`
+    /// 
+    /// Centered like `draw_layout_emphasized_to`, only the changed digits are animated
+    /// (see `draw_segments_transition`). Layouts with a different number of cells are
+    /// not animated. Hidden blinking cells stay hidden during the transition.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_layout_transition_to<D, C>(
+        &self,
+        target: &mut D,
+        from: &Layout,
+        to: &Layout,
+        transition: &Transition,
+        color: C,
+        inactive_color: Option<C>,
+        emphasis: &Emphasis<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor + Into<Rgb888> + From<Rgb888>,
+    {
+        let (from_text, to_text) = (from.text(), to.text());
+        let (from_text, to_text) = (from_text.as_str(), to_text.as_str());
+        if transition.is_finished() || cells(from_text).count() != cells(to_text).count() {
+            return self.draw_layout_emphasized_to(target, to, color, inactive_color, emphasis);
+        }
 
-        // Draw minutes units
-        self.draw_digit_to_fbuf(fbuf, minutes % 10, position, color, inactive_color);
-        position.x += digit_width as i32 + digit_spacing as i32;
+        let mut position = self.layout_position(target.bounding_box(), to_text);
 
-        // Draw second colon
-        self.draw_colon_to_fbuf(fbuf, position, color);
-        position.x += segment_width as i32 + digit_spacing as i32;
+        for (i, ((old, old_dp), (c, dp))) in cells(from_text).zip(cells(to_text)).enumerate() {
+            let cell_color = emphasis.cell_color(i, color);
+            match cell_color {
+                Some(cell_color) if c != ':' && (old, old_dp) != (c, dp) => {
+                    let (old, new) = (cell_segments(old, old_dp), cell_segments(c, dp));
+                    self.draw_segments_transition(target, &old, &new, position, transition, cell_color, inactive_color)?;
+                }
+                _ => self.draw_cell(target, c, dp, position, cell_color, inactive_color)?,
+            }
+            position.x += cell_width(&self.config, c) as i32 + self.config.digit_spacing as i32;
+        }
+        Ok(())
+    }
 
-        // Draw seconds tens
-        self.draw_digit_to_fbuf(fbuf, seconds / 10, position, color, inactive_color);
-        position.x += digit_width as i32 + digit_spacing as i32;
+    /// Top left of the text of a layout centered in `area`
+    pub(crate) fn layout_position(&self, area: Rectangle, text: &str) -> Point {
+        let total_width = self.str_width(text);
+        let start_x = (area.size.width.saturating_sub(total_width)) as i32 / 2;
+        let start_y = (area.size.height.saturating_sub(self.config.digit_size.height)) as i32 / 2;
+        area.top_left + Point::new(start_x, start_y)
+    }
 
-        // Draw seconds units
-        self.draw_digit_to_fbuf(fbuf, seconds % 10, position, color, inactive_color);
+    /// Area of each cell of a string drawn at `position`
+    /// 
+    /// A cell reaches up to the next cell, so it covers the decimal point, plus the
+    /// slant of skewed segments
+    pub(crate) fn cell_areas<'a>(&'a self, text: &'a str, position: Point) -> impl Iterator<Item = Rectangle> + 'a {
+        let cfg = &self.config;
+        let mut x = position.x;
+        cells(text).map(move |(c, _)| {
+            let width = cell_width(cfg, c) + cfg.digit_spacing;
+            let area = Rectangle::new(Point::new(x, position.y), Size::new(width + cfg.slant(), cfg.digit_size.height));
+            x += width as i32;
+            area
+        })
+    }
 
-        // Calculate display position (centered)
-        let display_center = display.bounding_box().center();
-        let target_point = Point::new(
-            display_center.x - (fbuf_size.width as i32 / 2),
-            display_center.y - (fbuf_size.height as i32 / 2),
-        );
-        
-        // Use fill_contiguous to write to display in one operation, avoid flicker
-        let area = Rectangle::new(target_point, fbuf_size);
-        display.fill_contiguous(&area, fbuf.data.iter().copied())?;
-        
-        Ok(())
+    /// Draw time (format: HH:MM:SS) centered in the target's bounding box
+    /// 
+    /// Same as `draw_layout_to` with `Layout::HoursMinutesSeconds`
+    /// 
+    /// inactive_color: color for inactive segments (dim effect), None to hide completely
+    pub fn draw_time_to<D, C>(
+        &self,
+        target: &mut D,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        color: C,
+        inactive_color: Option<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        let layout = Layout::HoursMinutesSeconds { hours, minutes, seconds };
+        self.draw_layout_to(target, &layout, color, inactive_color)
     }
 
-    /// Calculate total width of time display
-    pub fn time_display_width(config: &SevenSegmentConfig) -> u32 {
-        let digit_width = config.digit_size.width;
-        let digit_spacing = config.digit_spacing;
-        let segment_width = config.segment_width;
-        
-        // 6 digits + 2 colons + spacing
-        digit_width * 6 + segment_width * 2 + digit_spacing * 7
+    /// Draw single digit to frame buffer
+    pub fn draw_digit_to_fbuf<C, const N: usize>(
+        &self,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        digit: u8,
+        position: Point,
+        color: C,
+        inactive_color: Option<C>,
+    ) where
+        C: PixelColor + Default,
+    {
+        let _ = self.draw_digit(fbuf, digit, position, color, inactive_color);
+    }
+
+    /// Draw segments to frame buffer
+    /// 
+    /// inactive_color: color for inactive segments (dim effect), None to hide completely
+    pub fn draw_segments_to_fbuf<C, const N: usize>(
+        &self,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        segments: &Segments,
+        position: Point,
+        color: C,
+        inactive_color: Option<C>,
+    ) where
+        C: PixelColor + Default,
+    {
+        let _ = self.draw_segments(fbuf, segments, position, color, inactive_color);
+    }
+
+    /// Draw colon to frame buffer
+    pub fn draw_colon_to_fbuf<C, const N: usize>(
+        &self,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        position: Point,
+        color: C,
+    ) where
+        C: PixelColor + Default,
+    {
+        let _ = self.draw_colon(fbuf, position, color);
+    }
+
+    /// Draw a layout to frame buffer and display
+    /// 
+    /// The frame buffer is cleared, drawn with `draw_layout_to` and pushed to the center
+    /// of the display with a single `fill_contiguous`, avoiding flicker
+    /// 
+    /// inactive_color: color for inactive segments (dim effect), None to hide completely
+    pub fn draw_layout<T, C, const N: usize>(
+        &self,
+        display: &mut T,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        layout: &Layout,
+        color: C,
+        inactive_color: Option<C>,
+    ) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+        C: PixelColor + Default,
+    {
+        self.draw_layout_emphasized(display, fbuf, layout, color, inactive_color, &Emphasis::none())
+    }
+
+    /// Draw a layout with blinking and highlighted cells to frame buffer and display
+    /// 
+    /// See `draw_layout` and `draw_str_emphasized`
+    pub fn draw_layout_emphasized<T, C, const N: usize>(
+        &self,
+        display: &mut T,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        layout: &Layout,
+        color: C,
+        inactive_color: Option<C>,
+        emphasis: &Emphasis<C>,
+    ) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+        C: PixelColor + Default,
+    {
+        // Clear frame buffer
+        for pixel in fbuf.data.iter_mut() {
+            *pixel = C::default();
+        }
+
+        let _ = self.draw_layout_emphasized_to(fbuf, layout, color, inactive_color, emphasis);
+        self.push_fbuf(display, fbuf)
+    }
+
+    /// Draw the frame `transition.frame` of a layout transition to frame buffer and display
+    /// 
+    /// See `draw_layout` and `draw_layout_transition_to`
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_layout_transition<T, C, const N: usize>(
+        &self,
+        display: &mut T,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        from: &Layout,
+        to: &Layout,
+        transition: &Transition,
+        color: C,
+        inactive_color: Option<C>,
+        emphasis: &Emphasis<C>,
+    ) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+        C: PixelColor + Default + Into<Rgb888> + From<Rgb888>,
+    {
+        // Clear frame buffer
+        for pixel in fbuf.data.iter_mut() {
+            *pixel = C::default();
+        }
+
+        let _ = self.draw_layout_transition_to(fbuf, from, to, transition, color, inactive_color, emphasis);
+        self.push_fbuf(display, fbuf)
+    }
+
+    /// Push the frame buffer to the center of the display
+    pub(crate) fn push_fbuf<T, C, const N: usize>(&self, display: &mut T, fbuf: &FrameBuf<C, &mut [C; N]>) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+        C: PixelColor + Default,
+    {
+        // Use fill_contiguous to write to display in one operation, avoid flicker
+        let area = Rectangle::new(fbuf_origin(display, fbuf), fbuf.size());
+        display.fill_contiguous(&area, fbuf.data.iter().copied())?;
+        
+        Ok(())
+    }
+
+    /// Push part of the frame buffer (`area` in frame buffer coordinates) to the display
+    /// 
+    /// The frame buffer is placed at the center of the display like `push_fbuf`
+    pub(crate) fn push_fbuf_area<T, C, const N: usize>(
+        &self,
+        display: &mut T,
+        fbuf: &FrameBuf<C, &mut [C; N]>,
+        area: &Rectangle,
+    ) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+        C: PixelColor + Default,
+    {
+        let area = area.intersection(&fbuf.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let width = fbuf.size().width as usize;
+        let pixels = area.points().map(|p| fbuf.data[p.y as usize * width + p.x as usize]);
+        display.fill_contiguous(&area.translate(fbuf_origin(display, fbuf)), pixels)
+    }
+
+    /// Draw time to frame buffer and display (format: HH:MM:SS)
+    /// 
+    /// Same as `draw_layout` with `Layout::HoursMinutesSeconds`
+    /// 
+    /// inactive_color: color for inactive segments (dim effect), None to hide completely
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_time<T, C, const N: usize>(
+        &self,
+        display: &mut T,
+        fbuf: &mut FrameBuf<C, &mut [C; N]>,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        color: C,
+        inactive_color: Option<C>,
+    ) -> Result<(), T::Error>
+    where
+        T: DrawTarget<Color = C>,
+        C: PixelColor + Default,
+    {
+        let layout = Layout::HoursMinutesSeconds { hours, minutes, seconds };
+        self.draw_layout(display, fbuf, &layout, color, inactive_color)
+    }
+
+    /// Calculate total width of time display (HH:MM:SS), see `layout_width` for other layouts
+    pub fn time_display_width(config: &SevenSegmentConfig) -> u32 {
+        let digit_width = config.digit_size.width;
+        let digit_spacing = config.digit_spacing;
+        let segment_width = config.segment_width;
+        
+        // 6 digits + 2 colons + spacing (+ slant of the last digit when skewed)
+        digit_width * 6 + segment_width * 2 + digit_spacing * 7 + config.slant()
+    }
+}
+
+/// 14/16 segment bit definitions
+/// 
+/// Segment layout (the 14 segment display draws A1+A2 and D1+D2 as single bars):
+/// ```text
+///    A1A1 A2A2
+///   F H  I  J B
+///   F  H I J  B
+///    G1G1 G2G2
+///   E  K L M  C
+///   E K  L  M C
+///    D1D1 D2D2  DP
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlphaSegments(pub u32);
+
+impl AlphaSegments {
+    /// Segment A1 (top horizontal, left half)
+    pub const A1: u32 = 1 << 0;
+    /// Segment A2 (top horizontal, right half)
+    pub const A2: u32 = 1 << 1;
+    /// Segment B (top right vertical)
+    pub const B: u32 = 1 << 2;
+    /// Segment C (bottom right vertical)
+    pub const C: u32 = 1 << 3;
+    /// Segment D1 (bottom horizontal, left half)
+    pub const D1: u32 = 1 << 4;
+    /// Segment D2 (bottom horizontal, right half)
+    pub const D2: u32 = 1 << 5;
+    /// Segment E (bottom left vertical)
+    pub const E: u32 = 1 << 6;
+    /// Segment F (top left vertical)
+    pub const F: u32 = 1 << 7;
+    /// Segment G1 (middle horizontal, left half)
+    pub const G1: u32 = 1 << 8;
+    /// Segment G2 (middle horizontal, right half)
+    pub const G2: u32 = 1 << 9;
+    /// Segment H (top left diagonal)
+    pub const H: u32 = 1 << 10;
+    /// Segment I (top center vertical)
+    pub const I: u32 = 1 << 11;
+    /// Segment J (top right diagonal)
+    pub const J: u32 = 1 << 12;
+    /// Segment K (bottom left diagonal)
+    pub const K: u32 = 1 << 13;
+    /// Segment L (bottom center vertical)
+    pub const L: u32 = 1 << 14;
+    /// Segment M (bottom right diagonal)
+    pub const M: u32 = 1 << 15;
+    /// Decimal point
+    pub const DP: u32 = 1 << 16;
+
+    /// Whole top horizontal (A1 + A2)
+    pub const A: u32 = Self::A1 | Self::A2;
+    /// Whole bottom horizontal (D1 + D2)
+    pub const D: u32 = Self::D1 | Self::D2;
+    /// Whole middle horizontal (G1 + G2)
+    pub const G: u32 = Self::G1 | Self::G2;
+
+    /// Create empty segments
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Create segments from character
+    /// 
+    /// Supports digits, letters (lowercase is drawn as uppercase), space and
+    /// `- + * / \ _ = ( ) < > ' °`. Returns None for other characters.
+    pub fn from_char(c: char) -> Option<Self> {
+        let bits = match c.to_ascii_uppercase() {
+            '0' => Self::A | Self::B | Self::C | Self::D | Self::E | Self::F | Self::J | Self::K,
+            '1' => Self::B | Self::C | Self::J,
+            '2' => Self::A | Self::B | Self::D | Self::E | Self::G,
+            '3' => Self::A | Self::B | Self::C | Self::D | Self::G2,
+            '4' => Self::B | Self::C | Self::F | Self::G,
+            '5' => Self::A | Self::C | Self::D | Self::F | Self::G,
+            '6' => Self::A | Self::C | Self::D | Self::E | Self::F | Self::G,
+            '7' => Self::A | Self::B | Self::C,
+            '8' => Self::A | Self::B | Self::C | Self::D | Self::E | Self::F | Self::G,
+            '9' => Self::A | Self::B | Self::C | Self::D | Self::F | Self::G,
+            'A' => Self::A | Self::B | Self::C | Self::E | Self::F | Self::G,
+            'B' => Self::A | Self::B | Self::C | Self::D | Self::G2 | Self::I | Self::L,
+            'C' => Self::A | Self::D | Self::E | Self::F,
+            'D' => Self::A | Self::B | Self::C | Self::D | Self::I | Self::L,
+            'E' => Self::A | Self::D | Self::E | Self::F | Self::G1,
+            'F' => Self::A | Self::E | Self::F | Self::G1,
+            'G' => Self::A | Self::C | Self::D | Self::E | Self::F | Self::G2,
+            'H' => Self::B | Self::C | Self::E | Self::F | Self::G,
+            'I' => Self::A | Self::D | Self::I | Self::L,
+            'J' => Self::B | Self::C | Self::D | Self::E,
+            'K' => Self::E | Self::F | Self::G1 | Self::J | Self::M,
+            'L' => Self::D | Self::E | Self::F,
+            'M' => Self::B | Self::C | Self::E | Self::F | Self::H | Self::J,
+            'N' => Self::B | Self::C | Self::E | Self::F | Self::H | Self::M,
+            'O' => Self::A | Self::B | Self::C | Self::D | Self::E | Self::F,
+            'P' => Self::A | Self::B | Self::E | Self::F | Self::G,
+            'Q' => Self::A | Self::B | Self::C | Self::D | Self::E | Self::F | Self::M,
+            'R' => Self::A | Self::B | Self::E | Self::F | Self::G | Self::M,
+            'S' => Self::A | Self::C | Self::D | Self::G2 | Self::H,
+            'T' => Self::A | Self::I | Self::L,
+            'U' => Self::B | Self::C | Self::D | Self::E | Self::F,
+            'V' => Self::E | Self::F | Self::J | Self::K,
+            'W' => Self::B | Self::C | Self::E | Self::F | Self::K | Self::M,
+            'X' => Self::H | Self::J | Self::K | Self::M,
+            'Y' => Self::H | Self::J | Self::L,
+            'Z' => Self::A | Self::D | Self::J | Self::K,
+            '-' => Self::G,
+            '+' => Self::G | Self::I | Self::L,
+            '*' => Self::G | Self::H | Self::I | Self::J | Self::K | Self::L | Self::M,
+            '/' => Self::J | Self::K,
+            '\\' => Self::H | Self::M,
+            '_' => Self::D,
+            '=' => Self::D | Self::G,
+            '(' | '<' => Self::J | Self::M,
+            ')' | '>' => Self::H | Self::K,
+            '\'' => Self::I,
+            '°' => Self::A1 | Self::F | Self::G1 | Self::I,
+            ' ' => 0,
+            _ => return None,
+        };
+        Some(Self(bits))
+    }
+
+    /// Check if contains any of the given segments
+    pub fn contains(&self, segment: u32) -> bool {
+        (self.0 & segment) != 0
+    }
+}
+
+impl core::ops::BitOr<u32> for AlphaSegments {
+    type Output = AlphaSegments;
+    fn bitor(self, rhs: u32) -> Self::Output {
+        AlphaSegments(self.0 | rhs)
+    }
+}
+
+/// Shape of one segment of a 14/16 segment digit
+enum Shape {
+    /// Hexagon bar (horizontal or vertical)
+    Bar(Rectangle),
+    /// Diagonal inside the rectangle, `true` when rising to the right
+    Diagonal(Rectangle, bool),
+}
+
+/// 14 and 16 segment display drawer
+/// 
+/// `N` is the number of segments (14 or 16), use the `FourteenSegmentDisplay` and
+/// `SixteenSegmentDisplay` aliases. Bars are drawn with the same hexagon segments as
+/// `SevenSegmentDisplay`, diagonals fill the space between them.
+pub struct AlphaSegmentDisplay<const N: usize> {
+    config: SevenSegmentConfig,
+}
+
+/// 14 segment display (A and D are single bars)
+pub type FourteenSegmentDisplay = AlphaSegmentDisplay<14>;
+
+/// 16 segment display (A and D are split in halves)
+pub type SixteenSegmentDisplay = AlphaSegmentDisplay<16>;
+
+impl<const N: usize> AlphaSegmentDisplay<N> {
+    /// Create new 14/16 segment display
+    pub fn new(config: SevenSegmentConfig) -> Self {
+        Self { config }
+    }
+
+    /// Draw single character to any draw target (unsupported characters are blank)
+    pub fn draw_char<D, C>(
+        &self,
+        target: &mut D,
+        c: char,
+        position: Point,
+        color: C,
+        inactive_color: Option<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        let segments = AlphaSegments::from_char(c).unwrap_or(AlphaSegments::empty());
+        self.draw_segments(target, &segments, position, color, inactive_color)
+    }
+
+    /// Draw segments to any draw target
+    /// 
+    /// inactive_color: color for inactive segments (dim effect), None to hide completely
+    pub fn draw_segments<D, C>(
+        &self,
+        target: &mut D,
+        segments: &AlphaSegments,
+        position: Point,
+        color: C,
+        inactive_color: Option<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        let cfg = &self.config;
+        let sw = cfg.segment_width as i32;
+        let w = cfg.digit_size.width as i32;
+        let h = cfg.digit_size.height as i32;
+        let target = &mut Skew::new(cfg, target, position);
+
+        // Center vertical (I, L) and middle horizontal (G1, G2) positions
+        let mid_x = w / 2 - sw / 2;
+        let mid_y = h / 2 - sw / 2;
+
+        let rect = |x: i32, y: i32, width: i32, height: i32| {
+            Rectangle::new(position + Point::new(x, y), Size::new(width.max(0) as u32, height.max(0) as u32))
+        };
+
+        // Horizontal bars, split in halves at the center vertical for 16 segments
+        let split = |left: u32, right: u32, y: i32| {
+            if N == 16 {
+                [
+                    (left, Shape::Bar(rect(0, y, mid_x + sw, sw))),
+                    (right, Shape::Bar(rect(mid_x, y, w - mid_x, sw))),
+                ]
+            } else {
+                [(left | right, Shape::Bar(rect(0, y, w, sw))), (0, Shape::Bar(Rectangle::zero()))]
+            }
+        };
+        let [a1, a2] = split(AlphaSegments::A1, AlphaSegments::A2, 0);
+        let [d1, d2] = split(AlphaSegments::D1, AlphaSegments::D2, h - sw);
+
+        // Full width A/D bars (14 segments) cover the top of I and the bottom of L
+        let center_inset = if N == 16 { 0 } else { sw };
+
+        // Diagonals span the space between the bars, half as thick so they stay slanted
+        let (upper_y, upper_h) = (sw, mid_y - sw);
+        let (lower_y, lower_h) = (mid_y + sw, h - mid_y - 2 * sw);
+        let (left_x, left_w) = (sw, mid_x - sw);
+        let (right_x, right_w) = (mid_x + sw, w - mid_x - 2 * sw);
+        let diagonal_width = cfg.segment_width.div_ceil(2);
+
+        let shapes = [
+            a1,
+            a2,
+            (AlphaSegments::F, Shape::Bar(rect(0, 0, sw, h / 2))),
+            (AlphaSegments::B, Shape::Bar(rect(w - sw, 0, sw, h / 2))),
+            (AlphaSegments::I, Shape::Bar(rect(mid_x, center_inset, sw, h / 2 - center_inset))),
+            (AlphaSegments::H, Shape::Diagonal(rect(left_x, upper_y, left_w, upper_h), false)),
+            (AlphaSegments::J, Shape::Diagonal(rect(right_x, upper_y, right_w, upper_h), true)),
+            (AlphaSegments::G1, Shape::Bar(rect(0, mid_y, mid_x + sw, sw))),
+            (AlphaSegments::G2, Shape::Bar(rect(mid_x, mid_y, w - mid_x, sw))),
+            (AlphaSegments::E, Shape::Bar(rect(0, h / 2, sw, h / 2))),
+            (AlphaSegments::C, Shape::Bar(rect(w - sw, h / 2, sw, h / 2))),
+            (AlphaSegments::L, Shape::Bar(rect(mid_x, h / 2, sw, h / 2 - center_inset))),
+            (AlphaSegments::K, Shape::Diagonal(rect(left_x, lower_y, left_w, lower_h), true)),
+            (AlphaSegments::M, Shape::Diagonal(rect(right_x, lower_y, right_w, lower_h), false)),
+            d1,
+            d2,
+        ];
+
+        for (mask, shape) in shapes {
+            if mask == 0 {
+                continue;
+            }
+            let shape_color = if segments.contains(mask) {
+                color
+            } else if let Some(inactive) = inactive_color {
+                inactive
+            } else {
+                continue;
+            };
+            match shape {
+                Shape::Bar(rect) => draw_segment(target, reduced_rect(rect), cfg.segment_style, shape_color)?,
+                Shape::Diagonal(rect, rising) => {
+                    draw_diagonal_segment(target, rect, diagonal_width, rising, shape_color)?
+                }
+            }
+        }
+
+        // The decimal point lies outside the digit cell, only draw it when lit
+        if segments.contains(AlphaSegments::DP) {
+            draw_decimal_point(cfg, target, position, color)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draw a string, one character cell per character
+    /// 
+    /// Same rules as `SevenSegmentDisplay::draw_str`: `:` draws a colon, `.` lights the
+    /// decimal point of the previous character. Returns the position after the last character.
+    pub fn draw_str<D, C>(
+        &self,
+        target: &mut D,
+        text: &str,
+        position: Point,
+        color: C,
+        inactive_color: Option<C>,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        let mut position = position;
+        for (c, dp) in cells(text) {
+            if c == ':' {
+                draw_colon(&self.config, target, position, color)?;
+            } else {
+                let mut segments = AlphaSegments::from_char(c).unwrap_or(AlphaSegments::empty());
+                if dp {
+                    segments = segments | AlphaSegments::DP;
+                }
+                self.draw_segments(target, &segments, position, color, inactive_color)?;
+            }
+            position.x += cell_width(&self.config, c) as i32 + self.config.digit_spacing as i32;
+        }
+        Ok(position)
+    }
+
+    /// Calculate width of a string drawn with `draw_str`
+    pub fn str_width(&self, text: &str) -> u32 {
+        text_width(&self.config, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    fn small() -> SevenSegmentDisplay {
+        SevenSegmentDisplay::new(SevenSegmentConfig::new(Size::new(8, 14), 1, 2))
+    }
+
+    #[test]
+    fn digit_on_mock_display() {
+        let mut display = MockDisplay::new();
+        small().draw_digit(&mut display, 8, Point::zero(), BinaryColor::On, None).unwrap();
+        display.assert_pattern(&[
+            "  ####  ",
+            "  ####  ",
+            "##    ##",
+            "##    ##",
+            "##    ##",
+            "        ",
+            "  ####  ",
+            "  ####  ",
+            "        ",
+            "##    ##",
+            "##    ##",
+            "##    ##",
+            "  ####  ",
+            "  ####  ",
+        ]);
+    }
+
+    #[test]
+    fn inactive_segments_use_inactive_color() {
+        let mut display = MockDisplay::new();
+        small()
+            .draw_digit(&mut display, 1, Point::new(1, 0), BinaryColor::On, Some(BinaryColor::Off))
+            .unwrap();
+        display.assert_pattern(&[
+            "   ....  ",
+            "   ....  ",
+            " ..    ##",
+            " ..    ##",
+            " ..    ##",
+            "         ",
+            "   ....  ",
+            "   ....  ",
+            "         ",
+            " ..    ##",
+            " ..    ##",
+            " ..    ##",
+            "   ....  ",
+            "   ....  ",
+        ]);
+    }
+
+    #[test]
+    fn cropped_target_offsets_drawing() {
+        let mut display = MockDisplay::new();
+        let area = Rectangle::new(Point::new(10, 20), Size::new(8, 14));
+        small()
+            .draw_digit(&mut display.cropped(&area), 8, Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        assert_eq!(display.affected_area(), area);
+        assert_eq!(display.get_pixel(Point::new(12, 20)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(10, 22)), Some(BinaryColor::On));
+    }
+
+    #[test]
+    fn frame_buffer_path_matches_draw_target_path() {
+        let ss = SevenSegmentDisplay::new(SevenSegmentConfig::new(Size::new(6, 11), 1, 2));
+        assert!(SevenSegmentDisplay::time_display_width(&ss.config) <= 62);
+
+        // FrameBuf 路径：清空缓冲区、绘制、整块推送到屏幕中央
+        let mut data = [BinaryColor::Off; 62 * 16];
+        let mut fbuf = FrameBuf::new(&mut data, 62, 16);
+        let mut via_fbuf = MockDisplay::new();
+        ss.draw_time(&mut via_fbuf, &mut fbuf, 12, 34, 56, BinaryColor::On, None).unwrap();
+
+        // 直接绘制到屏幕上同一区域
+        let area = Rectangle::new(Point::new(0, 23), Size::new(62, 16));
+        let mut direct = MockDisplay::new();
+        direct.set_allow_overdraw(true);
+        direct.fill_solid(&area, BinaryColor::Off).unwrap();
+        ss.draw_time_to(&mut direct.cropped(&area), 12, 34, 56, BinaryColor::On, None).unwrap();
+
+        assert_eq!(via_fbuf, direct);
+    }
+
+    #[test]
+    fn from_char_covers_hex_digits() {
+        let hex: std::vec::Vec<Segments> = "0123456789AbCdEF".chars().map(|c| Segments::from_char(c).unwrap()).collect();
+        for (i, a) in hex.iter().enumerate() {
+            for b in &hex[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        for digit in 0..10 {
+            assert_eq!(Segments::from_char(char::from(b'0' + digit)), Segments::from_digit(digit));
+        }
+    }
+
+    #[test]
+    fn from_char_falls_back_to_other_case() {
+        assert_eq!(Segments::from_char('a'), Segments::from_char('A'));
+        assert_eq!(Segments::from_char('B'), Segments::from_char('b'));
+        assert_eq!(Segments::from_char('e'), Segments::from_char('E'));
+        // Both forms exist and differ
+        assert_ne!(Segments::from_char('C'), Segments::from_char('c'));
+        assert_ne!(Segments::from_char('U'), Segments::from_char('u'));
+        // Not drawable on 7 segments
+        assert_eq!(Segments::from_char('x'), None);
+        assert_eq!(Segments::from_char('M'), None);
+        assert_eq!(Segments::from_char('%'), None);
+    }
+
+    #[test]
+    fn from_char_symbols() {
+        assert_eq!(Segments::from_char('-'), Some(Segments(Segments::G)));
+        assert_eq!(Segments::from_char('_'), Some(Segments(Segments::D)));
+        assert_eq!(Segments::from_char(' '), Some(Segments::empty()));
+        assert_eq!(
+            Segments::from_char('°'),
+            Some(Segments(Segments::A | Segments::B | Segments::F | Segments::G))
+        );
+    }
+
+    #[test]
+    fn draw_str_places_each_character() {
+        let ss = small();
+        let mut text = MockDisplay::new();
+        let end = ss.draw_str(&mut text, "E:r", Point::zero(), BinaryColor::On, None).unwrap();
+        assert_eq!(end, Point::new(8 + 1 + 2 + 1 + 8 + 1, 0));
+        assert_eq!(ss.str_width("E:r"), 8 + 1 + 2 + 1 + 8);
+
+        let mut expected = MockDisplay::new();
+        ss.draw_segments(&mut expected, &Segments::from_char('E').unwrap(), Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        ss.draw_colon(&mut expected, Point::new(9, 0), BinaryColor::On).unwrap();
+        ss.draw_segments(&mut expected, &Segments::from_char('r').unwrap(), Point::new(12, 0), BinaryColor::On, None)
+            .unwrap();
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn unknown_characters_are_blank() {
+        let ss = small();
+        let mut display = MockDisplay::new();
+        ss.draw_str(&mut display, "%", Point::zero(), BinaryColor::On, Some(BinaryColor::Off)).unwrap();
+        let mut blank = MockDisplay::new();
+        ss.draw_digit(&mut blank, 8, Point::zero(), BinaryColor::Off, None).unwrap();
+        assert_eq!(display, blank);
+    }
+
+    #[test]
+    fn str_width_matches_time_layout() {
+        let ss = small();
+        assert_eq!(ss.str_width("88:88:88"), SevenSegmentDisplay::time_display_width(&ss.config));
+        assert_eq!(ss.str_width(""), 0);
+    }
+
+    #[test]
+    fn decimal_point_attaches_to_previous_character() {
+        let ss = SevenSegmentDisplay::new(SevenSegmentConfig::new(Size::new(8, 14), 4, 2));
+        assert_eq!(ss.str_width("1.5"), 8 + 4 + 8);
+
+        let mut text = MockDisplay::new();
+        ss.draw_str(&mut text, "1.5", Point::zero(), BinaryColor::On, None).unwrap();
+
+        let mut expected = MockDisplay::new();
+        ss.draw_segments(&mut expected, &(Segments::from_digit(1).unwrap() | Segments::DP), Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        ss.draw_digit(&mut expected, 5, Point::new(12, 0), BinaryColor::On, None).unwrap();
+        assert_eq!(text, expected);
+
+        // 小数点位于数字间隔的中间、底部对齐
+        for p in [Point::new(9, 12), Point::new(10, 12), Point::new(9, 13), Point::new(10, 13)] {
+            assert_eq!(text.get_pixel(p), Some(BinaryColor::On));
+        }
+    }
+
+    #[test]
+    fn leading_decimal_point_takes_its_own_cell() {
+        let ss = SevenSegmentDisplay::new(SevenSegmentConfig::new(Size::new(8, 14), 4, 2));
+        assert_eq!(ss.str_width(".5"), 8 + 4 + 8);
+        assert_eq!(ss.str_width("1:.5"), 8 + 4 + 2 + 4 + 8 + 4 + 8);
+
+        let mut text = MockDisplay::new();
+        ss.draw_str(&mut text, ".5", Point::zero(), BinaryColor::On, None).unwrap();
+
+        let mut expected = MockDisplay::new();
+        ss.draw_segments(&mut expected, &Segments(Segments::DP), Point::zero(), BinaryColor::On, None).unwrap();
+        ss.draw_digit(&mut expected, 5, Point::new(12, 0), BinaryColor::On, None).unwrap();
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn inactive_decimal_point_is_not_drawn() {
+        let ss = small();
+        let mut with_dp = MockDisplay::new();
+        ss.draw_segments(&mut with_dp, &Segments(Segments::DP), Point::zero(), BinaryColor::On, Some(BinaryColor::Off))
+            .unwrap();
+        let mut without_dp = MockDisplay::new();
+        ss.draw_segments(&mut without_dp, &Segments::empty(), Point::zero(), BinaryColor::On, Some(BinaryColor::Off))
+            .unwrap();
+        assert_ne!(with_dp, without_dp);
+        // 未点亮的小数点不会画到数字框外
+        assert_eq!(without_dp.affected_area(), Rectangle::new(Point::zero(), Size::new(8, 14)));
+    }
+
+    fn alpha_config() -> SevenSegmentConfig {
+        SevenSegmentConfig::new(Size::new(14, 22), 2, 2)
+    }
+
+    #[test]
+    fn sixteen_segment_diagonals() {
+        let mut display = MockDisplay::new();
+        SixteenSegmentDisplay::new(alpha_config())
+            .draw_char(&mut display, 'X', Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        display.assert_pattern(&[
+            "              ",
+            "              ",
+            "  #        #  ",
+            "  #        #  ",
+            "   #      #   ",
+            "   #      #   ",
+            "    #    #    ",
+            "    #    #    ",
+            "     #  #     ",
+            "     #  #     ",
+            "              ",
+            "              ",
+            "     #  #     ",
+            "     #  #     ",
+            "    #    #    ",
+            "    #    #    ",
+            "   #      #   ",
+            "   #      #   ",
+            "  #        #  ",
+            "  #        #  ",
+        ]);
+    }
+
+    #[test]
+    fn fourteen_segment_joins_top_and_bottom_halves() {
+        let sixteen = SixteenSegmentDisplay::new(alpha_config());
+        let fourteen = FourteenSegmentDisplay::new(alpha_config());
+
+        // 16 段的上下横段分成两半，各自可以单独点亮
+        let mut left_half = MockDisplay::new();
+        sixteen
+            .draw_segments(&mut left_half, &AlphaSegments(AlphaSegments::A1), Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        let mut whole = MockDisplay::new();
+        sixteen
+            .draw_segments(&mut whole, &AlphaSegments(AlphaSegments::A), Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        assert!(left_half.affected_area().size.width < whole.affected_area().size.width);
+
+        // 14 段只要点亮一半就画出整条
+        let mut joined = MockDisplay::new();
+        fourteen
+            .draw_segments(&mut joined, &AlphaSegments(AlphaSegments::A1), Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        assert_eq!(joined.affected_area(), Rectangle::new(Point::new(2, 0), Size::new(10, 2)));
+
+        // 其余段两种显示相同（中间竖段除外，14 段的中间竖段让开整条的上下横段）
+        for c in ['H', 'K', 'X', '1', '-'] {
+            let mut a = MockDisplay::new();
+            sixteen.draw_char(&mut a, c, Point::zero(), BinaryColor::On, None).unwrap();
+            let mut b = MockDisplay::new();
+            fourteen.draw_char(&mut b, c, Point::zero(), BinaryColor::On, None).unwrap();
+            assert_eq!(a, b, "{c}");
+        }
+    }
+
+    #[test]
+    fn alpha_from_char_covers_letters_and_digits() {
+        let glyphs: std::vec::Vec<AlphaSegments> = ('A'..='Z')
+            .chain('0'..='9')
+            .map(|c| AlphaSegments::from_char(c).unwrap())
+            .collect();
+        // '0' 与 'O' 靠斜线区分，所有字符互不相同
+        for (i, a) in glyphs.iter().enumerate() {
+            for b in &glyphs[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert_eq!(AlphaSegments::from_char('m'), AlphaSegments::from_char('M'));
+        assert_eq!(AlphaSegments::from_char(' '), Some(AlphaSegments::empty()));
+        assert_eq!(AlphaSegments::from_char('%'), None);
+    }
+
+    #[test]
+    fn alpha_draw_str_matches_seven_segment_layout() {
+        let cfg = alpha_config();
+        let sixteen = SixteenSegmentDisplay::new(cfg);
+        assert_eq!(sixteen.str_width("12:34.5"), SevenSegmentDisplay::new(cfg).str_width("12:34.5"));
+
+        let mut text = MockDisplay::new();
+        let end = sixteen.draw_str(&mut text, "A.B", Point::zero(), BinaryColor::On, None).unwrap();
+        assert_eq!(end, Point::new(14 + 2 + 14 + 2, 0));
+
+        let mut expected = MockDisplay::new();
+        let a = AlphaSegments::from_char('A').unwrap() | AlphaSegments::DP;
+        sixteen.draw_segments(&mut expected, &a, Point::zero(), BinaryColor::On, None).unwrap();
+        sixteen.draw_char(&mut expected, 'B', Point::new(16, 0), BinaryColor::On, None).unwrap();
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn alpha_segments_never_overdraw_with_inactive_segments() {
+        // 段宽较大时中间竖段的尖端不能伸进上下横段
+        let configs = [
+            SevenSegmentConfig::new(Size::new(25, 45), 4, 5),
+            SevenSegmentConfig::new(Size::new(24, 48), 4, 4),
+        ];
+        for cfg in configs {
+            for c in ['F', 'I', '+', 'X', '8'] {
+                let mut display = MockDisplay::new();
+                FourteenSegmentDisplay::new(cfg)
+                    .draw_char(&mut display, c, Point::zero(), BinaryColor::On, Some(BinaryColor::Off))
+                    .unwrap();
+                let mut display = MockDisplay::new();
+                SixteenSegmentDisplay::new(cfg)
+                    .draw_char(&mut display, c, Point::zero(), BinaryColor::On, Some(BinaryColor::Off))
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn layout_width_does_not_depend_on_value() {
+        let ss = small();
+        assert_eq!(ss.layout_width(&Layout::HoursMinutes { hours: 1, minutes: 2 }), ss.str_width("88:88"));
+        assert_eq!(
+            ss.layout_width(&Layout::TwelveHour { hours: 1, minutes: 2 }),
+            ss.layout_width(&Layout::TwelveHour { hours: 22, minutes: 2 })
+        );
+        assert_eq!(
+            ss.layout_width(&Layout::Integer { value: 5, digits: 4 }),
+            ss.layout_width(&Layout::Integer { value: -999, digits: 4 })
+        );
+        // 小数点画在数字间隔里，不增加宽度
+        assert_eq!(
+            ss.layout_width(&Layout::Fixed { value: 5, digits: 4, decimals: 2 }),
+            ss.layout_width(&Layout::Integer { value: 5, digits: 4 })
+        );
+        assert_eq!(
+            ss.layout_width(&Layout::HoursMinutesSeconds { hours: 0, minutes: 0, seconds: 0 }),
+            SevenSegmentDisplay::time_display_width(&ss.config)
+        );
+    }
+
+    #[test]
+    fn layout_is_centered_including_blank_cells() {
+        let ss = small();
+        let area = Rectangle::new(Point::new(5, 5), Size::new(40, 20));
+        let mut display = MockDisplay::new();
+        ss.draw_layout_to(
+            &mut display.cropped(&area),
+            &Layout::Integer { value: -7, digits: 3 },
+            BinaryColor::On,
+            Some(BinaryColor::Off),
+        )
+        .unwrap();
+
+        // 3 格宽 8+1+8+1+8 = 26，左右各留 7，上下各留 3
+        assert_eq!(display.affected_area(), Rectangle::new(Point::new(5 + 7, 5 + 3), Size::new(26, 14)));
+
+        let mut expected = MockDisplay::new();
+        ss.draw_str(&mut expected, " -7", Point::new(12, 8), BinaryColor::On, Some(BinaryColor::Off))
+            .unwrap();
+        assert_eq!(display, expected);
+    }
+
+    #[test]
+    fn hidden_blinking_cells_show_as_inactive() {
+        let ss = small();
+        let layout = Layout::HoursMinutes { hours: 12, minutes: 34 };
+        let area = Rectangle::new(Point::zero(), Size::new(60, 14));
+        let blink = Emphasis::none().blink(layout.colon_cells() | layout.digit_cell(3));
+
+        // 显示的半秒与普通绘制相同
+        let mut shown = MockDisplay::new();
+        ss.draw_layout_emphasized_to(&mut shown.cropped(&area), &layout, BinaryColor::On, Some(BinaryColor::Off), &blink.with_phase(0))
+            .unwrap();
+        let mut plain = MockDisplay::new();
+        ss.draw_layout_to(&mut plain.cropped(&area), &layout, BinaryColor::On, Some(BinaryColor::Off)).unwrap();
+        assert_eq!(shown, plain);
+
+        // 隐藏的半秒：冒号和最后一位只剩暗色的段
+        let mut hidden = MockDisplay::new();
+        ss.draw_layout_emphasized_to(&mut hidden.cropped(&area), &layout, BinaryColor::On, Some(BinaryColor::Off), &blink.with_phase(500))
+            .unwrap();
+        let mut expected = MockDisplay::new();
+        // 宽度 8+1+8+1+2+1+8+1+8 = 38，居中后从 x=11 开始
+        ss.draw_str(&mut expected, "12", Point::new(11, 0), BinaryColor::On, Some(BinaryColor::Off)).unwrap();
+        ss.draw_colon(&mut expected, Point::new(29, 0), BinaryColor::Off).unwrap();
+        ss.draw_str(&mut expected, "3 ", Point::new(32, 0), BinaryColor::On, Some(BinaryColor::Off)).unwrap();
+        assert_eq!(hidden, expected);
+
+        // 没有暗色时隐藏的格什么都不画
+        let mut bare = MockDisplay::new();
+        ss.draw_layout_emphasized_to(&mut bare.cropped(&area), &layout, BinaryColor::On, None, &blink.with_phase(500))
+            .unwrap();
+        assert_eq!(bare.affected_area().bottom_right(), Some(Point::new(39, 13)));
+    }
+
+    #[test]
+    fn highlighted_cells_use_highlight_color() {
+        let ss = small();
+        let emphasis = Emphasis::none().highlight(0b10, BinaryColor::Off);
+        let mut display = MockDisplay::new();
+        ss.draw_str_emphasized(&mut display, "88", Point::zero(), BinaryColor::On, None, &emphasis).unwrap();
+
+        let mut expected = MockDisplay::new();
+        ss.draw_digit(&mut expected, 8, Point::zero(), BinaryColor::On, None).unwrap();
+        ss.draw_digit(&mut expected, 8, Point::new(9, 0), BinaryColor::Off, None).unwrap();
+        assert_eq!(display, expected);
+    }
+
+    #[test]
+    fn fade_interpolates_changing_segments() {
+        use embedded_graphics::pixelcolor::{Rgb565, RgbColor};
+
+        let ss = small();
+        let (on, off) = (Rgb565::GREEN, Rgb565::new(0, 8, 0));
+        let mut transition = Transition::new(TransitionStyle::Fade, 4);
+        transition.restart();
+        transition.advance();
+        transition.advance();
+
+        // 1 -> 7：A 段逐渐点亮，B/C 段保持，其余保持暗色
+        let mut display = MockDisplay::new();
+        let (one, seven) = (Segments::from_digit(1).unwrap(), Segments::from_digit(7).unwrap());
+        ss.draw_segments_transition(&mut display, &one, &seven, Point::zero(), &transition, on, Some(off))
+            .unwrap();
+        let half = blend(off, on, 2, 4);
+        assert_ne!(half, on);
+        assert_ne!(half, off);
+        assert_eq!(display.get_pixel(Point::new(3, 0)), Some(half));
+        assert_eq!(display.get_pixel(Point::new(7, 3)), Some(on));
+        assert_eq!(display.get_pixel(Point::new(3, 13)), Some(off));
+
+        // 7 -> 1：A 段逐渐熄灭
+        let mut display = MockDisplay::new();
+        ss.draw_segments_transition(&mut display, &seven, &one, Point::zero(), &transition, on, Some(off))
+            .unwrap();
+        assert_eq!(display.get_pixel(Point::new(3, 0)), Some(blend(on, off, 2, 4)));
+    }
+
+    #[test]
+    fn fade_first_and_last_frames_show_old_and_new_digit() {
+        let ss = small();
+        let (from, to) = (Segments::from_digit(3).unwrap(), Segments::from_digit(4).unwrap());
+        let mut transition = Transition::new(TransitionStyle::Fade, 3);
+        transition.restart();
+
+        let mut first = MockDisplay::new();
+        ss.draw_segments_transition(&mut first, &from, &to, Point::zero(), &transition, BinaryColor::On, Some(BinaryColor::Off))
+            .unwrap();
+        let mut old = MockDisplay::new();
+        ss.draw_segments(&mut old, &from, Point::zero(), BinaryColor::On, Some(BinaryColor::Off)).unwrap();
+        assert_eq!(first, old);
+
+        transition.finish();
+        let mut last = MockDisplay::new();
+        ss.draw_segments_transition(&mut last, &from, &to, Point::zero(), &transition, BinaryColor::On, Some(BinaryColor::Off))
+            .unwrap();
+        let mut new = MockDisplay::new();
+        ss.draw_segments(&mut new, &to, Point::zero(), BinaryColor::On, Some(BinaryColor::Off)).unwrap();
+        assert_eq!(last, new);
+    }
+
+    #[test]
+    fn slide_up_halfway() {
+        let ss = small();
+        let mut transition = Transition::new(TransitionStyle::SlideUp, 2);
+        transition.restart();
+        transition.advance();
+
+        // 8 向上移出一半，1 从下方移入一半，超出数字格的部分被裁掉
+        let mut display = MockDisplay::new();
+        let (eight, one) = (Segments::from_digit(8).unwrap(), Segments::from_digit(1).unwrap());
+        ss.draw_segments_transition(&mut display, &eight, &one, Point::zero(), &transition, BinaryColor::On, None)
+            .unwrap();
+        display.assert_pattern(&[
+            "  ####  ",
+            "        ",
+            "##    ##",
+            "##    ##",
+            "##    ##",
+            "  ####  ",
+            "  ####  ",
+            "        ",
+            "        ",
+            "      ##",
+            "      ##",
+            "      ##",
+        ]);
+    }
+
+    #[test]
+    fn slide_down_keeps_inactive_segments_in_place() {
+        let ss = small();
+        let mut transition = Transition::new(TransitionStyle::SlideDown, 14);
+        transition.restart();
+        let (from, to) = (Segments::from_digit(2).unwrap(), Segments::from_digit(5).unwrap());
+
+        for frame in 1..14 {
+            transition.frame = frame;
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            ss.draw_segments_transition(&mut display, &from, &to, Point::zero(), &transition, BinaryColor::On, Some(BinaryColor::Off))
+                .unwrap();
+            // 暗色的段不动，点亮的段只在数字格内
+            let mut ghost = MockDisplay::new();
+            ss.draw_segments(&mut ghost, &Segments::empty(), Point::zero(), BinaryColor::Off, Some(BinaryColor::Off))
+                .unwrap();
+            for point in ghost.affected_area().points().filter(|&p| ghost.get_pixel(p).is_some()) {
+                assert!(display.get_pixel(point).is_some());
+            }
+            assert_eq!(display.affected_area(), Rectangle::new(Point::zero(), Size::new(8, 14)));
+        }
+    }
+
+    #[test]
+    fn layout_transition_only_animates_changed_digits() {
+        let ss = small();
+        let area = Rectangle::new(Point::zero(), Size::new(60, 14));
+        let (from, to) = (Layout::HoursMinutes { hours: 12, minutes: 39 }, Layout::HoursMinutes { hours: 12, minutes: 40 });
+        let mut transition = Transition::new(TransitionStyle::SlideUp, 2);
+        transition.restart();
+        transition.advance();
+
+        let mut display = MockDisplay::new();
+        ss.draw_layout_transition_to(&mut display.cropped(&area), &from, &to, &transition, BinaryColor::On, None, &Emphasis::none())
+            .unwrap();
+
+        // 居中后从 x=11 开始："12" 和冒号不变，后两位各自滚动
+        let mut expected = MockDisplay::new();
+        ss.draw_str(&mut expected, "12:", Point::new(11, 0), BinaryColor::On, None).unwrap();
+        for (x, old, new) in [(32, 3, 4), (41, 9, 0)] {
+            let (old, new) = (Segments::from_digit(old).unwrap(), Segments::from_digit(new).unwrap());
+            ss.draw_segments_transition(&mut expected, &old, &new, Point::new(x, 0), &transition, BinaryColor::On, None)
+                .unwrap();
+        }
+        assert_eq!(display, expected);
+
+        // 结束后与直接绘制新值相同
+        transition.finish();
+        let mut last = MockDisplay::new();
+        ss.draw_layout_transition_to(&mut last.cropped(&area), &from, &to, &transition, BinaryColor::On, None, &Emphasis::none())
+            .unwrap();
+        let mut new = MockDisplay::new();
+        ss.draw_layout_to(&mut new.cropped(&area), &to, BinaryColor::On, None).unwrap();
+        assert_eq!(last, new);
+    }
+
+    fn styled(style: SegmentStyle) -> SevenSegmentDisplay {
+        SevenSegmentDisplay::new(SevenSegmentConfig::new(Size::new(18, 30), 5, 5).with_style(style))
+    }
+
+    #[test]
+    fn skewed_segments_snapshot() {
+        let mut display = MockDisplay::new();
+        styled(SegmentStyle::Skewed(4)).draw_digit(&mut display, 8, Point::zero(), BinaryColor::On, None).unwrap();
+        display.assert_pattern(&[
+            "         ########     ",
+            "        ##########    ",
+            "       ############   ",
+            "      # ########## #  ",
+            "    ### ######## ###  ",
+            "   #####        ##### ",
+            "   #####        ##### ",
+            "   #####        ##### ",
+            "   #####        ##### ",
+            "   #####        ##### ",
+            "    ###          ###  ",
+            "    #            #    ",
+            "                      ",
+            "       ########       ",
+            "      ##########      ",
+            "     ############     ",
+            "      ##########      ",
+            "       ########       ",
+            "    #            #    ",
+            "  ###          ###    ",
+            " #####        #####   ",
+            " #####        #####   ",
+            " #####        #####   ",
+            " #####        #####   ",
+            " #####        #####   ",
+            "  ### ######## ###    ",
+            "  # ########## #      ",
+            "   ############       ",
+            "    ##########        ",
+            "     ########         ",
+        ]);
+    }
+
+    #[test]
+    fn rounded_segments_snapshot() {
+        let mut display = MockDisplay::new();
+        styled(SegmentStyle::Rounded).draw_digit(&mut display, 8, Point::zero(), BinaryColor::On, None).unwrap();
+        display.assert_pattern(&[
+            "       ####       ",
+            "      ######      ",
+            "      ######      ",
+            " ###  ######  ### ",
+            "#####  ####  #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            " ###          ### ",
+            "                  ",
+            "       ####       ",
+            "      ######      ",
+            "      ######      ",
+            "      ######      ",
+            "       ####       ",
+            " ###          ### ",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####  ####  #####",
+            " ###  ######  ### ",
+            "      ######      ",
+            "      ######      ",
+            "       ####       ",
+        ]);
+    }
+
+    #[test]
+    fn plain_segments_snapshot() {
+        let mut display = MockDisplay::new();
+        styled(SegmentStyle::Plain).draw_digit(&mut display, 8, Point::zero(), BinaryColor::On, None).unwrap();
+        display.assert_pattern(&[
+            "      ######      ",
+            "      ######      ",
+            "      ######      ",
+            "##### ###### #####",
+            "##### ###### #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "                  ",
+            "      ######      ",
+            "      ######      ",
+            "      ######      ",
+            "      ######      ",
+            "      ######      ",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "#####        #####",
+            "##### ###### #####",
+            "##### ###### #####",
+            "      ######      ",
+            "      ######      ",
+            "      ######      ",
+        ]);
+    }
+
+    #[test]
+    fn styles_never_overdraw_with_inactive_segments() {
+        // MockDisplay 默认不允许重复绘制同一像素，各段之间不能重叠
+        for style in [SegmentStyle::Hexagon, SegmentStyle::Skewed(4), SegmentStyle::Rounded, SegmentStyle::Plain] {
+            let mut display = MockDisplay::new();
+            styled(style)
+                .draw_str(&mut display, "1.:", Point::zero(), BinaryColor::On, Some(BinaryColor::Off))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn narrow_digits_draw_with_every_style() {
+        // 数字很窄时圆角两端的缩进会超过段长，不能溢出
+        for style in [SegmentStyle::Hexagon, SegmentStyle::Skewed(4), SegmentStyle::Rounded, SegmentStyle::Plain] {
+            for width in 10..=20 {
+                let cfg = SevenSegmentConfig::new(Size::new(width, 30), 2, 5).with_style(style);
+                let mut display = MockDisplay::new();
+                display.set_allow_overdraw(true);
+                SevenSegmentDisplay::new(cfg)
+                    .draw_str(&mut display, "8.", Point::zero(), BinaryColor::On, Some(BinaryColor::Off))
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn skewed_width_includes_slant() {
+        let ss = styled(SegmentStyle::Skewed(4));
+        assert_eq!(ss.str_width("8"), 18 + 4);
+        assert_eq!(ss.str_width(""), 0);
+        assert_eq!(ss.str_width("88:88:88"), SevenSegmentDisplay::time_display_width(&ss.config));
+
+        // 居中绘制时倾斜部分也在目标区域内
+        let area = Rectangle::new(Point::new(3, 5), Size::new(22, 30));
+        let mut display = MockDisplay::new();
+        ss.draw_segments(&mut display.cropped(&area), &Segments(0x7f), Point::zero(), BinaryColor::On, None)
+            .unwrap();
+        let affected = display.affected_area();
+        assert!(area.contains(affected.top_left));
+        assert!(area.contains(affected.bottom_right().unwrap()));
     }
 }
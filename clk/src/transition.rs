@@ -0,0 +1,123 @@
+//! Digit transition animations
+//!
+//! A `Transition` runs over a fixed number of frames. The caller advances it once per
+//! frame and redraws with `SevenSegmentDisplay::draw_segments_transition` or
+//! `draw_layout_transition_to`, frame 0 shows the old value and the last frame the new one.
+
+use embedded_graphics::pixelcolor::{Rgb888, RgbColor};
+
+/// How a changed digit turns into the new one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionStyle {
+    /// Segments that turn on or off fade between `inactive_color` and `color`
+    Fade,
+    /// The old digit moves up out of the cell, the new one rolls in from below
+    SlideUp,
+    /// The old digit moves down out of the cell, the new one rolls in from above
+    SlideDown,
+}
+
+/// A running transition, advanced by the caller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// Animation style
+    pub style: TransitionStyle,
+    /// Number of frames from the old value to the new one
+    pub frames: u32,
+    /// Current frame, 0 shows the old value and `frames` the new one
+    pub frame: u32,
+}
+
+impl Transition {
+    /// Create a finished transition (shows the new value until restarted)
+    pub const fn new(style: TransitionStyle, frames: u32) -> Self {
+        Self {
+            style,
+            frames,
+            frame: frames,
+        }
+    }
+
+    /// Start again from the old value
+    pub fn restart(&mut self) {
+        self.frame = 0;
+    }
+
+    /// Jump to the new value
+    pub fn finish(&mut self) {
+        self.frame = self.frames;
+    }
+
+    /// Advance one frame, returns false when the transition was already finished
+    pub fn advance(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.frame += 1;
+        true
+    }
+
+    /// Check if the new value is fully shown
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.frames
+    }
+}
+
+/// Interpolate between two colors, `step` of `steps` (0 gives `from`, `steps` gives `to`)
+pub fn blend<C>(from: C, to: C, step: u32, steps: u32) -> C
+where
+    C: Into<Rgb888> + From<Rgb888>,
+{
+    if steps == 0 || step >= steps {
+        return to;
+    }
+    let (from, to): (Rgb888, Rgb888) = (from.into(), to.into());
+    let channel = |a: u8, b: u8| {
+        let (a, b, step, steps) = (a as i32, b as i32, step as i32, steps as i32);
+        (a + ((b - a) * step * 2 + steps).div_euclid(steps * 2)) as u8
+    };
+    C::from(Rgb888::new(
+        channel(from.r(), to.r()),
+        channel(from.g(), to.g()),
+        channel(from.b(), to.b()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::pixelcolor::Rgb565;
+
+    #[test]
+    fn blend_endpoints_are_exact() {
+        let (a, b) = (Rgb565::new(1, 2, 3), Rgb565::new(30, 60, 29));
+        assert_eq!(blend(a, b, 0, 5), a);
+        assert_eq!(blend(a, b, 5, 5), b);
+        assert_eq!(blend(a, b, 9, 5), b);
+        assert_eq!(blend(a, b, 0, 0), b);
+    }
+
+    #[test]
+    fn blend_steps_evenly() {
+        let steps: std::vec::Vec<Rgb888> = (0..=4).map(|i| blend(Rgb888::BLACK, Rgb888::WHITE, i, 4)).collect();
+        assert_eq!(steps.iter().map(|c| c.r()).collect::<std::vec::Vec<_>>(), [0, 64, 128, 191, 255]);
+        // 反方向对称
+        assert_eq!(blend(Rgb888::WHITE, Rgb888::BLACK, 1, 4).g(), 255 - 64);
+    }
+
+    #[test]
+    fn advance_stops_at_last_frame() {
+        let mut transition = Transition::new(TransitionStyle::Fade, 3);
+        assert!(transition.is_finished());
+        assert!(!transition.advance());
+
+        transition.restart();
+        assert_eq!(transition.frame, 0);
+        assert!(transition.advance());
+        assert!(transition.advance());
+        assert!(transition.advance());
+        assert!(transition.is_finished());
+        assert!(!transition.advance());
+        assert_eq!(transition.frame, 3);
+    }
+}
@@ -0,0 +1,186 @@
+#!/usr/bin/env python3
+"""
+生成贪吃蛇的图块图集 sprites.tga（24 位真彩色 TGA，tinytga 可直接读取）
+
+图集由 10x10 像素的图块组成，共 14 列 3 行：
+
+    第 0 行：第一条蛇   蛇头(上 下 左 右) 蛇尾(上 下 左 右) 横 竖 拐角(上左 上右 下左 下右)
+    第 1 行：第二条蛇   同上
+    第 2 行：食物(普通 奖励 缩短 加速) 障碍物
+
+蛇头方向为前进方向，蛇尾方向为指向前一节的方向，拐角按连接的两侧命名。
+颜色与 src/render.rs 中的颜色常量一致（以 RGB565 的分量给出）。
+
+用法: python3 make_sprites.py [输出文件]
+"""
+
+import sys
+
+TILE = 10
+COLS = 14
+ROWS = 3
+
+BLACK = (0, 0, 0)
+WHITE = (31, 63, 31)
+
+# 第一条蛇：蛇头 GREEN，蛇身 BODY_COLOR
+SNAKE1 = {"head": (0, 63, 0), "body": (0, 25, 0), "edge": (0, 12, 0)}
+# 第二条蛇：蛇头 PLAYER2_HEAD_COLOR，蛇身 PLAYER2_BODY_COLOR
+SNAKE2 = {"head": (0, 63, 31), "body": (0, 25, 25), "edge": (0, 12, 12)}
+
+FOOD_NORMAL = (31, 0, 0)
+FOOD_BONUS = (31, 63, 0)
+FOOD_SHRINK = (31, 0, 31)
+FOOD_SPEED_UP = (31, 32, 0)
+
+WALL = (16, 32, 16)
+MORTAR = (8, 16, 8)
+
+
+def rotate(shape, direction):
+    """把朝右的图形旋转到指定方向"""
+    last = TILE - 1
+    if direction == "right":
+        return set(shape)
+    if direction == "left":
+        return {(last - x, last - y) for x, y in shape}
+    if direction == "up":
+        return {(y, last - x) for x, y in shape}
+    if direction == "down":
+        return {(last - y, x) for x, y in shape}
+    raise ValueError(direction)
+
+
+def flip(shape, horizontal, vertical):
+    last = TILE - 1
+    return {(last - x if horizontal else x, last - y if vertical else y) for x, y in shape}
+
+
+def outline(shape):
+    """图形的轮廓：上下左右有一侧不在图形内的像素（图块边界外视为相连）"""
+    edge = set()
+    for x, y in shape:
+        for nx, ny in ((x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)):
+            if 0 <= nx < TILE and 0 <= ny < TILE and (nx, ny) not in shape:
+                edge.add((x, y))
+    return edge
+
+
+def paint(shape, fill, edge_color):
+    tile = {}
+    for p in shape:
+        tile[p] = fill
+    for p in outline(shape):
+        tile[p] = edge_color
+    return tile
+
+
+def band_right():
+    """从中心向右的半截蛇身"""
+    return {(x, y) for x in range(2, TILE) for y in range(2, 8)}
+
+
+def head_right():
+    shape = {(x, y) for x in range(0, 9) for y in range(1, 9)}
+    shape -= {(8, 1), (8, 2), (7, 1), (8, 7), (8, 8), (7, 8)}
+    return shape
+
+
+def tail_right():
+    shape = set()
+    for x in range(2, TILE):
+        half = 1 if x < 4 else 2 if x < 6 else 3
+        for y in range(5 - half, 5 + half):
+            shape.add((x, y))
+    return shape
+
+
+def snake_tiles(colors):
+    tiles = []
+    for direction in ("up", "down", "left", "right"):
+        shape = rotate(head_right(), direction)
+        tile = paint(shape, colors["head"], colors["body"])
+        # 眼睛
+        for eye, pupil in (((6, 3), (7, 3)), ((6, 6), (7, 6))):
+            (ex, ey), = rotate({eye}, direction)
+            (px, py), = rotate({pupil}, direction)
+            tile[(ex, ey)] = WHITE
+            tile[(px, py)] = BLACK
+        tiles.append(tile)
+    for direction in ("up", "down", "left", "right"):
+        tiles.append(paint(rotate(tail_right(), direction), colors["body"], colors["edge"]))
+
+    horizontal = band_right() | rotate(band_right(), "left")
+    tiles.append(paint(horizontal, colors["body"], colors["edge"]))
+    tiles.append(paint(rotate(horizontal, "up"), colors["body"], colors["edge"]))
+
+    # 拐角：以“下右”为基准翻转
+    down_right = band_right() | rotate(band_right(), "down")
+    down_right.discard((2, 2))
+    for horizontal_flip, vertical_flip in ((True, True), (False, True), (True, False), (False, False)):
+        shape = flip(down_right, horizontal_flip, vertical_flip)
+        tiles.append(paint(shape, colors["body"], colors["edge"]))
+    return tiles
+
+
+def food_tiles():
+    def circle(r2):
+        return {(x, y) for x in range(TILE) for y in range(TILE) if (x - 4.5) ** 2 + (y - 4.5) ** 2 <= r2}
+
+    diamond = {(x, y) for x in range(TILE) for y in range(TILE) if abs(x - 4.5) + abs(y - 4.5) <= 4.5}
+    plus = {(x, y) for x in range(TILE) for y in range(TILE) if 3 <= x <= 6 or 3 <= y <= 6}
+    plus &= circle(17)
+
+    tiles = []
+    for shape, color in ((circle(13), FOOD_NORMAL), (diamond, FOOD_BONUS), (circle(7), FOOD_SHRINK), (plus, FOOD_SPEED_UP)):
+        tile = {p: color for p in shape}
+        tile[(3, 3)] = WHITE
+        tiles.append(tile)
+    return tiles
+
+
+def wall_tile():
+    tile = {}
+    for x in range(TILE):
+        for y in range(TILE):
+            offset = 0 if y < 5 else 5
+            mortar = y in (4, 9) or (x + offset) % TILE == 9
+            tile[(x, y)] = MORTAR if mortar else WALL
+    return tile
+
+
+def to_rgb888(color):
+    r, g, b = color
+    return (round(r * 255 / 31), round(g * 255 / 63), round(b * 255 / 31))
+
+
+def main():
+    output = sys.argv[1] if len(sys.argv) > 1 else "sprites.tga"
+
+    rows = [snake_tiles(SNAKE1), snake_tiles(SNAKE2), food_tiles() + [wall_tile()]]
+    width, height = COLS * TILE, ROWS * TILE
+
+    pixels = bytearray()
+    for y in range(height):
+        row = rows[y // TILE]
+        for x in range(width):
+            col = x // TILE
+            tile = row[col] if col < len(row) else {}
+            r, g, b = to_rgb888(tile.get((x % TILE, y % TILE), BLACK))
+            pixels += bytes((b, g, r))
+
+    header = bytearray(18)
+    header[2] = 2                       # 无压缩真彩色
+    header[12:14] = width.to_bytes(2, "little")
+    header[14:16] = height.to_bytes(2, "little")
+    header[16] = 24                     # 每像素 24 位（BGR）
+    header[17] = 0x20                   # 原点在左上角
+
+    with open(output, "wb") as f:
+        f.write(header)
+        f.write(pixels)
+    print(f"已生成 {output}（{width}x{height}）")
+
+
+if __name__ == "__main__":
+    main()
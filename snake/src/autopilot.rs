@@ -0,0 +1,343 @@
+//! 电脑玩家模块
+//!
+//! 每个 tick 选择一个方向，代替摇杆交给 `Game::set_direction`：
+//!
+//! 1. 用 BFS 找到去食物的最短路径，沿路径走完后如果还能到达自己的尾巴，
+//!    就走这条路径的第一步；
+//! 2. 否则跟着尾巴走（选择离尾巴最远、且走完后仍能到达尾巴的方向），
+//!    等食物附近腾出空间；
+//! 3. 连尾巴也到不了时，选择可到达格子最多的方向，尽量多活几步。
+//!
+//! BFS 考虑了蛇身随时间移动：第 `i` 节（蛇头为第 0 节）在第
+//! `len - i + 1` 步之后才能进入（移动时的碰撞检查发生在蛇尾离开之前）。
+//! 双人模式下电脑控制第一条蛇，另一条蛇的身体同样按时间让出，
+//! 它的蛇头周围的格子当作不能进入（不知道对方下一步往哪走）。
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::snake::{Direction, Game, Position};
+
+/// 搜索时尝试的方向顺序
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+/// 未访问的格子
+const UNVISITED: usize = usize::MAX;
+
+/// 电脑玩家
+///
+/// 保存 BFS 用的缓冲区，避免每个 tick 重新分配
+#[derive(Default)]
+pub struct Autopilot {
+    /// 每格最早可以进入的步数（墙为 `u16::MAX`）
+    free_at: Vec<u16>,
+    /// BFS 中每格的上一格
+    prev: Vec<usize>,
+    queue: VecDeque<(Position, u16)>,
+}
+
+impl Autopilot {
+    /// 创建电脑玩家
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 选择下一步的方向，无论怎么走都会撞上时返回 None（保持当前方向）
+    ///
+    /// 每个 tick 在 `update` 之前调用一次，结果交给 `set_direction`
+    pub fn choose(&mut self, game: &Game) -> Option<Direction> {
+        let body: Vec<Position> = game.snake.iter().copied().collect();
+        let head = *body.first()?;
+
+        // 1. 去吃食物，吃完之后还要能到达尾巴
+        if let Some(path) = self.path(game, &body, game.food) {
+            let mut after: Vec<Position> = path.iter().rev().chain(&body).copied().collect();
+            after.truncate(game.snake.len() + 1);
+            let after_tail = *after.last().unwrap();
+            if self.path(game, &after, after_tail).is_some() {
+                return game.direction_between(head, path[0]);
+            }
+        }
+
+        // 2. 跟着尾巴走，选择绕得最远的方向
+        let mut best: Option<(Direction, usize)> = None;
+        for dir in DIRECTIONS {
+            let Some(next) = self.first_step(game, head, dir) else {
+                continue;
+            };
+            let after: Vec<Position> = core::iter::once(next)
+                .chain(body[..body.len() - 1].iter().copied())
+                .collect();
+            let after_tail = *after.last().unwrap();
+            if let Some(path) = self.path(game, &after, after_tail)
+                && best.is_none_or(|(_, len)| path.len() > len)
+            {
+                best = Some((dir, path.len()));
+            }
+        }
+        if let Some((dir, _)) = best {
+            return Some(dir);
+        }
+
+        // 3. 走向空间最大的一侧
+        let mut best: Option<(Direction, usize)> = None;
+        for dir in DIRECTIONS {
+            let Some(next) = self.first_step(game, head, dir) else {
+                continue;
+            };
+            let after: Vec<Position> = core::iter::once(next)
+                .chain(body[..body.len() - 1].iter().copied())
+                .collect();
+            let area = self.search(game, &after, None);
+            if best.is_none_or(|(_, most)| area > most) {
+                best = Some((dir, area));
+            }
+        }
+        best.map(|(dir, _)| dir)
+    }
+
+    /// 选择方向并交给游戏（代替摇杆输入）
+    pub fn steer(&mut self, game: &mut Game) {
+        if let Some(dir) = self.choose(game) {
+            game.set_direction(dir);
+        }
+    }
+
+    /// 从蛇头沿指定方向走一步，撞墙或撞到自己时返回 None
+    fn first_step(&self, game: &Game, head: Position, dir: Direction) -> Option<Position> {
+        let next = game.step(head, dir)?;
+        if game.walls.is_wall(next) || game.is_occupied(next) || self.near_rival(game, next) {
+            return None;
+        }
+        Some(next)
+    }
+
+    /// 格子是否紧挨着另一条蛇的蛇头（下一步可能头对头相撞）
+    fn near_rival(&self, game: &Game, pos: Position) -> bool {
+        game.player2
+            .as_ref()
+            .is_some_and(|p2| DIRECTIONS.into_iter().any(|dir| game.step(p2.snake[0], dir) == Some(pos)))
+    }
+
+    /// 从 `body[0]` 到 `goal` 的最短路径（不含起点），到不了时返回 None
+    fn path(&mut self, game: &Game, body: &[Position], goal: Position) -> Option<Vec<Position>> {
+        self.search(game, body, Some(goal));
+
+        let cols = game.config.cols as usize;
+        let start = index(cols, body[0]);
+        let mut i = index(cols, goal);
+        if self.prev[i] == UNVISITED {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        while i != start {
+            path.push(Position { x: (i % cols) as u16, y: (i / cols) as u16 });
+            i = self.prev[i];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// 从 `body[0]` 开始 BFS，到达 `goal` 时提前结束，返回访问过的格子数
+    fn search(&mut self, game: &Game, body: &[Position], goal: Option<Position>) -> usize {
+        let cols = game.config.cols as usize;
+        let cells = cols * game.config.rows as usize;
+
+        self.free_at.clear();
+        self.free_at.resize(cells, 0);
+        for pos in game.walls.walls() {
+            self.free_at[index(cols, pos)] = u16::MAX;
+        }
+        let len = body.len();
+        for (i, pos) in body.iter().enumerate().skip(1) {
+            self.free_at[index(cols, *pos)] = (len - i + 1) as u16;
+        }
+        if let Some(p2) = &game.player2 {
+            let len = p2.snake.len();
+            for (i, pos) in p2.snake.iter().enumerate() {
+                self.free_at[index(cols, *pos)] = (len - i + 1) as u16;
+            }
+            for dir in DIRECTIONS {
+                if let Some(next) = game.step(p2.snake[0], dir) {
+                    let slot = &mut self.free_at[index(cols, next)];
+                    *slot = (*slot).max(2);
+                }
+            }
+        }
+
+        self.prev.clear();
+        self.prev.resize(cells, UNVISITED);
+        let start = index(cols, body[0]);
+        self.prev[start] = start;
+
+        self.queue.clear();
+        self.queue.push_back((body[0], 0));
+        let mut visited = 0;
+        while let Some((pos, steps)) = self.queue.pop_front() {
+            for dir in DIRECTIONS {
+                let Some(next) = game.step(pos, dir) else {
+                    continue;
+                };
+                let i = index(cols, next);
+                if self.prev[i] != UNVISITED || self.free_at[i] > steps + 1 {
+                    continue;
+                }
+                self.prev[i] = index(cols, pos);
+                visited += 1;
+                if goal == Some(next) {
+                    return visited;
+                }
+                self.queue.push_back((next, steps + 1));
+            }
+        }
+        visited
+    }
+}
+
+fn index(cols: usize, pos: Position) -> usize {
+    pos.y as usize * cols + pos.x as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Body;
+    use crate::level::LEVELS;
+    use crate::snake::{BoardMode, GameConfig, GameState};
+
+    fn pos(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    /// 默认棋盘上的蛇身（蛇头在前）
+    fn body(cells: &[Position]) -> Body {
+        Body::from_cells(&GameConfig::default(), cells.iter().copied())
+    }
+
+    /// 让电脑玩一局，返回结束时的游戏
+    fn play(mut game: alloc::boxed::Box<Game>, max_ticks: usize) -> alloc::boxed::Box<Game> {
+        let mut pilot = Autopilot::new();
+        game.press_button();
+        for _ in 0..max_ticks {
+            if game.is_game_over() {
+                break;
+            }
+            pilot.steer(&mut game);
+            game.update();
+        }
+        game
+    }
+
+    #[test]
+    fn heads_straight_for_food() {
+        let mut game = Game::with_seed(1);
+        game.press_button();
+        let head = game.snake[0];
+        game.food = pos(head.x, head.y - 3);
+        assert_eq!(Autopilot::new().choose(&game), Some(Direction::Up));
+
+        game.food = pos(head.x + 5, head.y);
+        assert_eq!(Autopilot::new().choose(&game), Some(Direction::Right));
+    }
+
+    #[test]
+    fn never_reverses() {
+        let mut game = Game::with_seed(1);
+        game.press_button();
+        let head = game.snake[0];
+        // 食物在蛇的正后方
+        game.food = pos(head.x - 4, head.y);
+        let dir = Autopilot::new().choose(&game).unwrap();
+        assert_ne!(dir, Direction::Left);
+    }
+
+    #[test]
+    fn wraps_around_edges() {
+        let mut game = Game::with_mode(BoardMode::Wrap, 1);
+        game.press_button();
+        game.snake = body(&[pos(1, 5), pos(2, 5), pos(3, 5)]);
+        game.direction = Direction::Left;
+        game.food = pos(22, 5);
+        assert_eq!(Autopilot::new().choose(&game), Some(Direction::Left));
+    }
+
+    #[test]
+    fn follows_tail_when_food_is_unreachable() {
+        let config = GameConfig::new(6, 6, 10);
+        let mut game = Game::with_config(config, BoardMode::Walls, 1);
+        game.press_button();
+        // 食物被蛇身围住：
+        //   . . . . . .
+        //   . B B B . .
+        //   . B F B . .
+        //   . H . B . .
+        //   . T B B . .
+        game.snake = Body::from_cells(&config, [
+            pos(1, 3),
+            pos(1, 2),
+            pos(1, 1),
+            pos(2, 1),
+            pos(3, 1),
+            pos(3, 2),
+            pos(3, 3),
+            pos(3, 4),
+            pos(2, 4),
+            pos(1, 4),
+        ]);
+        game.direction = Direction::Down;
+        game.food = pos(2, 2);
+        let dir = Autopilot::new().choose(&game).unwrap();
+        // 进入 (2, 3) 会把自己困在食物格里，应该走向空处
+        assert_eq!(dir, Direction::Left);
+    }
+
+    #[test]
+    fn avoids_head_on_with_second_snake() {
+        let mut game = Game::with_seed(1);
+        game.set_two_player(true);
+        game.press_button();
+        game.snake = body(&[pos(5, 5), pos(4, 5), pos(3, 5)]);
+        game.player2.as_mut().unwrap().snake = body(&[pos(7, 5), pos(8, 5), pos(9, 5)]);
+        game.food = pos(10, 5);
+        let dir = Autopilot::new().choose(&game).unwrap();
+        assert!(dir == Direction::Up || dir == Direction::Down);
+    }
+
+    #[test]
+    fn scores_well_on_empty_board() {
+        let game = play(Game::with_seed(3), 20_000);
+        assert!(game.score >= 30, "score {}", game.score);
+    }
+
+    /// 长时间运行：多种种子、边界模式和关卡，检查每个 tick 的游戏状态是否自洽
+    #[test]
+    fn soak() {
+        let mut pilot = Autopilot::new();
+        for seed in 0..12u16 {
+            let mode = if seed % 2 == 0 { BoardMode::Walls } else { BoardMode::Wrap };
+            let mut game = Game::with_mode(mode, seed);
+            if seed % 3 == 0 {
+                game.set_levels(&LEVELS);
+            }
+            game.press_button();
+
+            for _ in 0..5_000 {
+                if game.state != GameState::Playing {
+                    break;
+                }
+                pilot.steer(&mut game);
+                game.update();
+
+                for (i, p) in game.snake.iter().enumerate() {
+                    assert!(p.x < game.config.cols && p.y < game.config.rows);
+                    assert!(!game.walls.is_wall(*p));
+                    assert!(!game.snake.iter().skip(i + 1).any(|q| q == p), "蛇身重叠");
+                }
+                assert!(!game.snake.contains(&game.food));
+                assert!(!game.walls.is_wall(game.food));
+            }
+            assert!(game.score > 0, "seed {} scored nothing", seed);
+        }
+    }
+}
@@ -8,6 +8,7 @@
 #![deny(clippy::large_stack_frames)]
 
 use core::cell::RefCell;
+use core::sync::atomic::{AtomicBool, Ordering};
 use critical_section::Mutex;
 use defmt::println;
 use defmt_rtt as _;
@@ -21,17 +22,24 @@ use esp_hal::{
     handler, ram,
     spi::master::Config, 
 };
-use embedded_graphics_framebuf::FrameBuf;
-use embedded_graphics::{prelude::{Primitive, RgbColor}, primitives::{PrimitiveStyle, Rectangle}};
+use embedded_graphics::prelude::RgbColor;
 use embedded_graphics::{
-    Drawable,
     pixelcolor::{Rgb565},
-    prelude::{DrawTarget, Point, Size},
+    prelude::DrawTarget,
 };
 use embedded_hal_bus::spi::ExclusiveDevice;
+use esp_bootloader_esp_idf::partitions;
+use esp_storage::FlashStorage;
 use mipidsi::{Builder, interface::SpiInterface, models::ST7789};
 
-use joystick::snake::{Game, Direction, GRID_SIZE, SCREEN_SIZE, set_random_seed};
+use joystick::autopilot::Autopilot;
+use joystick::food::FOOD_TABLE;
+use joystick::highscore::{Entry, HighScores};
+use joystick::level::LEVELS;
+use joystick::render::{draw_high_scores, draw_overlay};
+use joystick::replay::Recorder;
+use joystick::snake::{BoardMode, Difficulty, Direction, Game, GameConfig, GameState};
+use joystick::sprite::SpriteRenderer;
 
 #[panic_handler]
 fn panic(_: &core::panic::PanicInfo) -> ! {
@@ -42,8 +50,32 @@ extern crate alloc;
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
+// 棋盘配置（240x240 屏幕，24x24 格子，每格 10 像素）
+const BOARD: GameConfig = GameConfig::new(24, 24, 10);
+
+// 游戏难度（决定速度曲线）
+const DIFFICULTY: Difficulty = Difficulty::Normal;
+
+// 保存排行榜的 flash 分区（见 partitions.csv）
+const HISCORE_PARTITION: &str = "hiscore";
+
+// 双人模式（第二名玩家使用 GPIO3/GPIO4 上的第二个摇杆）
+const TWO_PLAYER: bool = false;
+
+// 方向判断参数
+const CENTER: u16 = 3900;       // 摇杆中点值
+const DEAD_ZONE: u16 = 100;     // 死区范围
+
+// 由电脑代替摇杆操作（无人值守的长时间运行测试）
+const AUTOPILOT: bool = false;
+
+// 非游戏状态（开始、暂停、结束画面）下的刷新间隔
+const IDLE_INTERVAL_MS: u32 = 50;
+
 // Z按钮使用GPIO中断（摇杆按下）
 static Z_BUTTON: Mutex<RefCell<Option<Input>>> = Mutex::new(RefCell::new(None));
+// 中断中记录按键，主循环中处理
+static Z_PRESSED: AtomicBool = AtomicBool::new(false);
 
 #[allow(clippy::large_stack_frames)]
 #[main]
@@ -62,13 +94,27 @@ fn main() -> ! {
     // 初始化随机数种子（使用 ESP32-S3 硬件 RNG）
     let mut rng = esp_hal::rng::Rng::new();
     let random_seed = rng.random();
-    set_random_seed(random_seed as u16);
     println!("随机种子: {}", random_seed);
 
+    // ========== 排行榜（从 flash 分区读取）==========
+    let mut flash = FlashStorage::new(peripherals.FLASH);
+    let mut pt_mem = [0u8; partitions::PARTITION_TABLE_MAX_LEN];
+    let pt = partitions::read_partition_table(&mut flash, &mut pt_mem).unwrap();
+    let hiscore_partition = pt
+        .iter()
+        .find(|p| p.label_as_str() == HISCORE_PARTITION)
+        .expect("partition table has no hiscore partition");
+    let mut hiscore_storage = hiscore_partition.as_embedded_storage(&mut flash);
+    let mut high_scores = HighScores::load(&mut hiscore_storage).unwrap_or_default();
+    println!("最高分: {}", high_scores.best().unwrap_or(0));
+
     // ========== ADC 配置（读取XY摇杆位置）==========
     let mut adc1_config = AdcConfig::new();
     let mut x_adc = adc1_config.enable_pin(peripherals.GPIO1, Attenuation::_11dB);
     let mut y_adc = adc1_config.enable_pin(peripherals.GPIO2, Attenuation::_11dB);
+    // 第二个摇杆（双人模式）
+    let mut x2_adc = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut y2_adc = adc1_config.enable_pin(peripherals.GPIO4, Attenuation::_11dB);
     let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
 
     // ========== Z按钮配置（GPIO中断检测按下）==========
@@ -106,20 +152,62 @@ fn main() -> ! {
         .unwrap();
     display.clear(Rgb565::BLACK).unwrap();
 
-    // 创建帧缓冲区（全屏 240x240，使用 Box 分配到堆上）
-    let data_boxed = alloc::boxed::Box::new([Rgb565::BLACK; 240 * 240]);
-    let data: &'static mut [Rgb565; 240 * 240] = alloc::boxed::Box::leak(data_boxed);
-    let mut fbuf: FrameBuf<Rgb565, &'static mut [Rgb565; 240 * 240]> = FrameBuf::new(data, 240, 240);
-
-    // 方向判断参数
-    const CENTER: u16 = 3900;       // 摇杆中点值
-    const DEAD_ZONE: u16 = 100;     // 死区范围
+    // 图块绘制器（用图集绘制蛇头、蛇身、拐角和蛇尾，只把变化的格子写到屏幕，
+    // 不需要全屏帧缓冲区；每格 10 像素与图块大小相同）
+    let mut renderer = SpriteRenderer::new();
 
     // 初始化游戏
-    let mut game = Game::new();
+    let mut seed = random_seed as u16;
+    let mut game = Game::with_config(BOARD, BoardMode::Walls, seed);
+    game.difficulty = DIFFICULTY;
+    // 设置顺序与 Replay::new_game 一致：食物概率表、双人模式、关卡
+    game.set_food_table(&FOOD_TABLE);
+    game.set_two_player(TWO_PLAYER);
+    game.set_levels(&LEVELS);
     println!("贪吃蛇游戏开始！");
 
+    // 录像（游戏结束时打印，可在主机上用 Replay::decode 重放）
+    let mut recorder = Recorder::new(&game, seed);
+
+    // 电脑玩家，开始画面上用它自动玩一局演示（无障碍物）
+    let mut autopilot = Autopilot::new();
+    let mut demo = Game::with_config(BOARD, BoardMode::Walls, rng.random() as u16);
+    demo.press_button();
+
     loop {
+        // 处理Z按钮：开始 / 暂停 / 继续 / 返回开始画面
+        if Z_PRESSED.swap(false, Ordering::Relaxed) {
+            if game.is_finished() {
+                // 新的一局使用新种子，便于单独重放
+                seed = rng.random() as u16;
+                game.reset_with_seed(seed);
+                recorder = Recorder::new(&game, seed);
+            } else {
+                game.press_button();
+            }
+            println!("状态: {}", game.state);
+            // 演示画面和游戏画面之间切换时整屏重绘
+            renderer.invalidate();
+        }
+
+        // 开始画面：电脑自动演示，按Z开始游戏
+        if game.state == GameState::Title {
+            autopilot.steer(&mut demo);
+            demo.update();
+            if demo.is_finished() {
+                demo.reset_with_seed(rng.random() as u16);
+                demo.press_button();
+            }
+
+            renderer.draw(&mut display, &demo).unwrap();
+            // 标题文字和排行榜每帧重画，蛇经过时不会被擦掉
+            draw_overlay(&mut display, &game).unwrap();
+            draw_high_scores(&mut display, &game, &high_scores).unwrap();
+
+            delay.delay_millis(demo.tick_interval_ms());
+            continue;
+        }
+
         // 读取摇杆
         let x_value: u16 = nb::block!(adc1.read_oneshot(&mut x_adc)).unwrap();
         let y_value: u16 = nb::block!(adc1.read_oneshot(&mut y_adc)).unwrap();
@@ -127,99 +215,98 @@ fn main() -> ! {
         // 打印摇杆值（调试用）
         // println!("X: {}, Y: {}", x_value, y_value);
 
-        // 方向判断（使用原始代码的逻辑）
-        let x_dir = if x_value < CENTER - DEAD_ZONE {
-            -1  // 左
-        } else if x_value > CENTER + DEAD_ZONE {
-            1   // 右
-        } else {
-            0   // 居中
-        };
+        let current_dir = joystick_direction(x_value, y_value);
 
-        let y_dir = if y_value < CENTER - DEAD_ZONE {
-            -1  // 上
-        } else if y_value > CENTER + DEAD_ZONE {
-            1   // 下
-        } else {
-            0   // 居中
-        };
-
-        // 根据方向组合确定最终方向
-        let current_dir = match (x_dir, y_dir) {
-            (-1, 0) => Some(Direction::Left),
-            (1, 0) => Some(Direction::Right),
-            (0, -1) => Some(Direction::Up),
-            (0, 1) => Some(Direction::Down),
-            (-1, -1) => Some(Direction::Left),
-            (-1, 1) => Some(Direction::Left),
-            (1, -1) => Some(Direction::Right),
-            (1, 1) => Some(Direction::Right),
-            _ => None,
-        };
-
-        // 直接设置方向
+        // 电脑玩家代替摇杆
+        let current_dir = if AUTOPILOT { autopilot.choose(&game) } else { current_dir };
+
+        // 加入转向队列，下次更新时生效
         if let Some(dir) = current_dir {
             println!("检测到方向: {:?}", dir);
-            game.set_direction(dir);
+            recorder.set_direction(&mut game, dir);
         }
 
-        // 游戏更新
-        game.update();
-
-        // 游戏结束检测
-        if game.game_over {
-            println!("游戏结束！最终得分: {}", game.score);
-            delay.delay_millis(2000);
-            game.reset();
-            println!("游戏重新开始！");
-            continue;
+        // 第二个摇杆
+        if TWO_PLAYER {
+            let x2_value: u16 = nb::block!(adc1.read_oneshot(&mut x2_adc)).unwrap();
+            let y2_value: u16 = nb::block!(adc1.read_oneshot(&mut y2_adc)).unwrap();
+            if let Some(dir) = joystick_direction(x2_value, y2_value) {
+                recorder.set_player2_direction(&mut game, dir);
+            }
         }
 
-        // 绘制游戏到帧缓冲区
-        draw_game_to_buffer(&mut fbuf, &game);
+        // 游戏更新
+        let was_playing = game.state == GameState::Playing;
+        recorder.update(&mut game);
 
-        // 将帧缓冲区绘制到屏幕
-        let area = Rectangle::new(Point::new(0, 0), Size::new(240, 240));
-        display.fill_contiguous(&area, fbuf.data.iter().copied()).unwrap();
+        // 游戏结束检测（停留在结束画面，按Z返回开始画面）
+        if was_playing && game.is_finished() {
+            if game.state == GameState::Cleared {
+                println!("棋盘已占满，获胜！");
+            }
+            println!("游戏结束！最终得分: {}，关卡: {}", game.score, game.level + 1);
 
-        // 游戏速度控制
-        delay.delay_millis(150);
-    }
-}
+            let replay = core::mem::replace(&mut recorder, Recorder::new(&game, seed)).finish(&game);
+            println!("录像: {=[u8]:x}", replay.encode().as_slice());
 
-/// 绘制游戏画面到帧缓冲区
-fn draw_game_to_buffer(fbuf: &mut FrameBuf<Rgb565, &mut [Rgb565; 240 * 240]>, game: &Game) {
-    // 清空缓冲区
-    for pixel in fbuf.data.iter_mut() {
-        *pixel = Rgb565::BLACK;
-    }
+            if let (Some(result), Some(p2)) = (game.result, &game.player2) {
+                println!("双人模式结果: {}，P1 {} : {} P2", result, game.score, p2.score);
+            }
+        }
+
+        // 排行榜只记录单人模式
+        if was_playing && game.is_finished() && !game.is_two_player() {
+            let entry = Entry { score: game.score, level: game.level as u8, difficulty: game.difficulty };
+            if let Some(rank) = high_scores.insert(entry) {
+                println!("进入排行榜第 {} 名", rank + 1);
+                if high_scores.save(&mut hiscore_storage).is_err() {
+                    println!("排行榜保存失败");
+                }
+            }
+        }
+
+        // 绘制到屏幕：游戏中只重绘变化的格子，状态切换时整屏重绘
+        renderer.draw(&mut display, &game).unwrap();
 
-    // 绘制蛇
-    for (i, pos) in game.snake.iter().enumerate() {
-        let color = if i == 0 {
-            Rgb565::GREEN  // 蛇头
+        // 游戏速度控制（随分数和难度变化）
+        if game.state == GameState::Playing {
+            delay.delay_millis(game.tick_interval_ms());
         } else {
-            Rgb565::new(0, 200 >> 3, 0)  // 蛇身（深绿色）
-        };
-        
-        let area = Rectangle::new(
-            Point::new((pos.x * GRID_SIZE) as i32, (pos.y * GRID_SIZE) as i32),
-            Size::new(GRID_SIZE as u32, GRID_SIZE as u32)
-        );
-        area.into_styled(PrimitiveStyle::with_fill(color)).draw(fbuf).unwrap();
+            delay.delay_millis(IDLE_INTERVAL_MS);
+        }
     }
+}
 
-    // 绘制食物
-    let food_area = Rectangle::new(
-        Point::new((game.food.x * GRID_SIZE) as i32, (game.food.y * GRID_SIZE) as i32),
-        Size::new(GRID_SIZE as u32, GRID_SIZE as u32)
-    );
-    food_area.into_styled(PrimitiveStyle::with_fill(Rgb565::RED)).draw(fbuf).unwrap();
-
-    // 绘制边框
-    let border_style = PrimitiveStyle::with_stroke(Rgb565::WHITE, 1);
-    let border = Rectangle::new(Point::new(0, 0), Size::new(SCREEN_SIZE as u32, SCREEN_SIZE as u32));
-    border.into_styled(border_style).draw(fbuf).unwrap();
+/// 根据摇杆读数判断方向（斜向时以左右为准）
+fn joystick_direction(x_value: u16, y_value: u16) -> Option<Direction> {
+    let x_dir = if x_value < CENTER - DEAD_ZONE {
+        -1  // 左
+    } else if x_value > CENTER + DEAD_ZONE {
+        1   // 右
+    } else {
+        0   // 居中
+    };
+
+    let y_dir = if y_value < CENTER - DEAD_ZONE {
+        -1  // 上
+    } else if y_value > CENTER + DEAD_ZONE {
+        1   // 下
+    } else {
+        0   // 居中
+    };
+
+    // 根据方向组合确定最终方向
+    match (x_dir, y_dir) {
+        (-1, 0) => Some(Direction::Left),
+        (1, 0) => Some(Direction::Right),
+        (0, -1) => Some(Direction::Up),
+        (0, 1) => Some(Direction::Down),
+        (-1, -1) => Some(Direction::Left),
+        (-1, 1) => Some(Direction::Left),
+        (1, -1) => Some(Direction::Right),
+        (1, 1) => Some(Direction::Right),
+        _ => None,
+    }
 }
 
 #[handler]
@@ -230,6 +317,7 @@ fn button_handler() {
         if let Some(ref mut btn) = *z_btn
             && btn.is_interrupt_set() {
                 println!("Button pressed!");
+                Z_PRESSED.store(true, Ordering::Relaxed);
                 btn.clear_interrupt();
             }
     });
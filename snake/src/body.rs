@@ -0,0 +1,299 @@
+//! 蛇身模块
+//!
+//! 蛇身保存在环形缓冲区（`VecDeque`）里，蛇头在前：移动时在头部加一格、
+//! 尾部去掉一格，都是 O(1)。另外用一张按棋盘大小分配的位图记录哪些格子
+//! 被蛇身占据，碰撞检查和生成食物时查询某一格也是 O(1)，不需要遍历蛇身。
+
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Index;
+
+use crate::snake::{GameConfig, Position};
+
+/// 蛇身（蛇头在前）
+///
+/// 同一条蛇的格子互不重叠（撞到自己时游戏已经结束）
+#[derive(Clone)]
+pub struct Body {
+    cells: VecDeque<Position>,
+    cols: u16,
+    rows: u16,
+    /// 占用位图，第 `y * cols + x` 位对应格子 (x, y)
+    bits: Vec<u32>,
+}
+
+impl Body {
+    /// 创建空的蛇身
+    pub fn new(config: &GameConfig) -> Self {
+        let cells = config.cols as usize * config.rows as usize;
+        Self {
+            cells: VecDeque::with_capacity(cells),
+            cols: config.cols,
+            rows: config.rows,
+            bits: vec![0; cells.div_ceil(32)],
+        }
+    }
+
+    /// 用给定的格子（蛇头在前）创建蛇身
+    pub fn from_cells(config: &GameConfig, cells: impl IntoIterator<Item = Position>) -> Self {
+        let mut body = Self::new(config);
+        for pos in cells {
+            body.push_back(pos);
+        }
+        body
+    }
+
+    /// 节数
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// 蛇头
+    pub fn head(&self) -> Option<Position> {
+        self.cells.front().copied()
+    }
+
+    /// 蛇尾
+    pub fn tail(&self) -> Option<Position> {
+        self.cells.back().copied()
+    }
+
+    /// 从蛇头到蛇尾遍历
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Position> + ExactSizeIterator {
+        self.cells.iter()
+    }
+
+    /// 格子是否被蛇身占据（O(1)），棋盘外的格子返回 false
+    pub fn contains(&self, pos: &Position) -> bool {
+        self.bit(*pos).is_some_and(|(word, mask)| self.bits[word] & mask != 0)
+    }
+
+    /// 在蛇头前加一格
+    pub fn push_front(&mut self, pos: Position) {
+        self.set(pos, true);
+        self.cells.push_front(pos);
+    }
+
+    /// 在蛇尾后加一格
+    pub fn push_back(&mut self, pos: Position) {
+        self.set(pos, true);
+        self.cells.push_back(pos);
+    }
+
+    /// 去掉蛇尾
+    pub fn pop_back(&mut self) -> Option<Position> {
+        let pos = self.cells.pop_back()?;
+        self.set(pos, false);
+        Some(pos)
+    }
+
+    /// 只保留前 `len` 节
+    pub fn truncate(&mut self, len: usize) {
+        while self.cells.len() > len {
+            self.pop_back();
+        }
+    }
+
+    /// 清空
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bits.fill(0);
+    }
+
+    fn bit(&self, pos: Position) -> Option<(usize, u32)> {
+        if pos.x >= self.cols || pos.y >= self.rows {
+            return None;
+        }
+        let i = pos.y as usize * self.cols as usize + pos.x as usize;
+        Some((i / 32, 1 << (i % 32)))
+    }
+
+    fn set(&mut self, pos: Position, occupied: bool) {
+        if let Some((word, mask)) = self.bit(pos) {
+            if occupied {
+                self.bits[word] |= mask;
+            } else {
+                self.bits[word] &= !mask;
+            }
+        }
+    }
+}
+
+impl Index<usize> for Body {
+    type Output = Position;
+
+    fn index(&self, index: usize) -> &Position {
+        &self.cells[index]
+    }
+}
+
+impl<'a> IntoIterator for &'a Body {
+    type Item = &'a Position;
+    type IntoIter = alloc::collections::vec_deque::Iter<'a, Position>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+impl PartialEq for Body {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<const N: usize> PartialEq<[Position; N]> for Body {
+    fn eq(&self, other: &[Position; N]) -> bool {
+        self.cells == *other
+    }
+}
+
+impl PartialEq<Vec<Position>> for Body {
+    fn eq(&self, other: &Vec<Position>) -> bool {
+        self.cells == *other
+    }
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.cells).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn occupancy_follows_moves() {
+        let config = GameConfig::new(24, 24, 10);
+        let mut body = Body::from_cells(&config, [pos(2, 0), pos(1, 0), pos(0, 0)]);
+        assert!(body.contains(&pos(0, 0)));
+
+        body.push_front(pos(3, 0));
+        body.pop_back();
+        assert_eq!(body, [pos(3, 0), pos(2, 0), pos(1, 0)]);
+        assert!(body.contains(&pos(3, 0)));
+        assert!(!body.contains(&pos(0, 0)));
+        assert_eq!(body.head(), Some(pos(3, 0)));
+        assert_eq!(body.tail(), Some(pos(1, 0)));
+    }
+
+    #[test]
+    fn truncate_and_clear_release_cells() {
+        let config = GameConfig::new(5, 5, 10);
+        let mut body = Body::from_cells(&config, [pos(4, 4), pos(3, 4), pos(2, 4), pos(1, 4)]);
+        body.truncate(2);
+        assert_eq!(body.len(), 2);
+        assert!(!body.contains(&pos(2, 4)));
+        assert!(body.contains(&pos(3, 4)));
+
+        body.clear();
+        assert!(body.is_empty());
+        assert!(!body.contains(&pos(4, 4)));
+    }
+
+    #[test]
+    fn outside_board_is_not_occupied() {
+        let config = GameConfig::new(4, 4, 10);
+        let body = Body::from_cells(&config, [pos(3, 3)]);
+        assert!(!body.contains(&pos(4, 3)));
+        assert!(!body.contains(&pos(3, 4)));
+    }
+
+    /// 主机上的性能对比（原来的 `Vec` 实现 vs 环形缓冲区 + 位图）：
+    ///
+    /// ```text
+    /// cargo +stable test --lib --release --target x86_64-unknown-linux-gnu -- --ignored --nocapture bench
+    /// ```
+    #[test]
+    #[ignore]
+    fn bench_body_vs_vec() {
+        use std::time::Instant;
+
+        const STEPS: usize = 200_000;
+        let config = GameConfig::new(24, 24, 10);
+
+        // 蛇在棋盘上按蛇形路线来回走，长度为棋盘的一半
+        let path: Vec<Position> = (0..config.rows)
+            .flat_map(|y| {
+                let row: Vec<u16> = if y % 2 == 0 { (0..config.cols).collect() } else { (0..config.cols).rev().collect() };
+                row.into_iter().map(move |x| pos(x, y))
+            })
+            .collect();
+        let len = path.len() / 2;
+
+        for len in [8, len] {
+            // 原实现：头部插入要移动整个 Vec，碰撞检查遍历蛇身
+            let start = Instant::now();
+            let mut vec: Vec<Position> = path[..len].iter().rev().copied().collect();
+            let mut hits = 0;
+            for step in 0..STEPS {
+                let next = path[(len + step) % path.len()];
+                if vec.contains(&next) {
+                    hits += 1;
+                }
+                vec.insert(0, next);
+                vec.pop();
+            }
+            let vec_time = start.elapsed();
+
+            let start = Instant::now();
+            let mut body = Body::from_cells(&config, path[..len].iter().rev().copied());
+            let mut body_hits = 0;
+            for step in 0..STEPS {
+                let next = path[(len + step) % path.len()];
+                if body.contains(&next) {
+                    body_hits += 1;
+                }
+                body.push_front(next);
+                body.pop_back();
+            }
+            let body_time = start.elapsed();
+
+            assert_eq!(hits, body_hits);
+            std::println!(
+                "move    len {:3}: Vec {:>10.2?}  Body {:>10.2?}  ({:.1}x)",
+                len,
+                vec_time,
+                body_time,
+                vec_time.as_secs_f64() / body_time.as_secs_f64()
+            );
+
+            // 生成食物时统计空格子：每格都要查询一次是否在蛇身上
+            let start = Instant::now();
+            let mut vec_free = 0;
+            for _ in 0..STEPS / 100 {
+                vec_free += path.iter().filter(|p| !vec.contains(p)).count();
+            }
+            let vec_time = start.elapsed();
+
+            let start = Instant::now();
+            let mut body_free = 0;
+            for _ in 0..STEPS / 100 {
+                body_free += path.iter().filter(|p| !body.contains(p)).count();
+            }
+            let body_time = start.elapsed();
+
+            assert_eq!(vec_free, body_free);
+            std::println!(
+                "spawn   len {:3}: Vec {:>10.2?}  Body {:>10.2?}  ({:.1}x)",
+                len,
+                vec_time,
+                body_time,
+                vec_time.as_secs_f64() / body_time.as_secs_f64()
+            );
+        }
+    }
+}
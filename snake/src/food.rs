@@ -0,0 +1,118 @@
+//! 食物种类模块
+//!
+//! 每次生成食物时按概率表抽取种类。除普通食物外都有存在时间，
+//! 超时未被吃掉就在别处重新生成。
+
+#[cfg(target_os = "none")]
+use defmt::Format;
+
+/// 食物种类
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[cfg_attr(target_os = "none", derive(Format))]
+pub enum FoodKind {
+    /// 普通食物：1 分，蛇身加长一节
+    #[default]
+    Normal,
+    /// 奖励食物：`BONUS_POINTS` 分，蛇身加长一节
+    Bonus,
+    /// 缩短食物：1 分，蛇身缩短 `SHRINK_LEN` 节（不短于 `MIN_LEN`）
+    Shrink,
+    /// 加速食物：1 分，蛇身加长一节，之后 `SPEED_UP_TICKS` 个 tick 内加速
+    SpeedUp,
+}
+
+impl FoodKind {
+    /// 吃到后得到的分数
+    pub const fn points(&self) -> u16 {
+        match self {
+            FoodKind::Bonus => BONUS_POINTS,
+            _ => 1,
+        }
+    }
+
+    /// 存在的 tick 数，普通食物一直存在
+    pub const fn lifetime(&self) -> Option<u16> {
+        match self {
+            FoodKind::Normal => None,
+            FoodKind::Bonus => Some(40),
+            FoodKind::Shrink => Some(60),
+            FoodKind::SpeedUp => Some(50),
+        }
+    }
+}
+
+/// 奖励食物的分数
+pub const BONUS_POINTS: u16 = 3;
+
+/// 缩短食物减少的节数
+pub const SHRINK_LEN: usize = 2;
+
+/// 缩短后蛇的最短长度（与初始长度相同）
+pub const MIN_LEN: usize = 3;
+
+/// 加速持续的 tick 数
+pub const SPEED_UP_TICKS: u16 = 40;
+
+/// 加速期间 tick 间隔占原来的百分比
+pub const SPEED_UP_PERCENT: u32 = 60;
+
+/// 只有普通食物（默认）
+pub const NORMAL_ONLY: [(FoodKind, u16); 1] = [(FoodKind::Normal, 1)];
+
+/// 内置概率表：（种类，权重）
+pub const FOOD_TABLE: [(FoodKind, u16); 4] = [
+    (FoodKind::Normal, 70),
+    (FoodKind::Bonus, 15),
+    (FoodKind::Shrink, 8),
+    (FoodKind::SpeedUp, 7),
+];
+
+/// 按权重从概率表中选出一种食物，`roll` 为随机数
+///
+/// 表中只有一项时直接返回该项，不需要随机数
+pub fn pick(table: &[(FoodKind, u16)], roll: u16) -> FoodKind {
+    let total: u32 = table.iter().map(|(_, weight)| *weight as u32).sum();
+    if total == 0 {
+        return FoodKind::Normal;
+    }
+
+    let mut roll = roll as u32 % total;
+    for (kind, weight) in table {
+        if roll < *weight as u32 {
+            return *kind;
+        }
+        roll -= *weight as u32;
+    }
+    FoodKind::Normal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_follows_weights() {
+        let mut counts = [0; 4];
+        for roll in 0..100 {
+            let kind = pick(&FOOD_TABLE, roll);
+            let i = FOOD_TABLE.iter().position(|(k, _)| *k == kind).unwrap();
+            counts[i] += 1;
+        }
+        assert_eq!(counts, [70, 15, 8, 7]);
+    }
+
+    #[test]
+    fn pick_handles_degenerate_tables() {
+        assert_eq!(pick(&NORMAL_ONLY, 12345), FoodKind::Normal);
+        assert_eq!(pick(&[], 7), FoodKind::Normal);
+        assert_eq!(pick(&[(FoodKind::Bonus, 0), (FoodKind::Shrink, 2)], 5), FoodKind::Shrink);
+    }
+
+    #[test]
+    fn only_normal_food_lasts_forever() {
+        assert_eq!(FoodKind::Normal.lifetime(), None);
+        for (kind, _) in &FOOD_TABLE[1..] {
+            assert!(kind.lifetime().is_some());
+        }
+    }
+}
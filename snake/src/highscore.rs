@@ -0,0 +1,335 @@
+//! 最高分排行榜模块
+//!
+//! 排行榜保存在 flash 分区中，每条记录单独带 CRC 校验：
+//!
+//! ```text
+//! 偏移  长度  内容
+//! 0     2     分数（小端）
+//! 2     1     关卡序号
+//! 3     1     难度
+//! 4     2     CRC-16/CCITT-FALSE（小端），覆盖前 4 个字节
+//! ```
+//!
+//! 读取时跳过校验失败或未写入（全 0xFF）的记录，其余记录重新排序，
+//! 下次保存时整个表被重新写入，损坏的记录随之清除。
+
+use alloc::vec::Vec;
+use embedded_storage::{ReadStorage, Storage};
+
+use crate::snake::Difficulty;
+
+/// 排行榜条数
+pub const TABLE_LEN: usize = 5;
+
+/// 每条记录的字节数
+pub const RECORD_LEN: usize = 6;
+
+/// 整个排行榜占用的字节数
+pub const TABLE_BYTES: usize = TABLE_LEN * RECORD_LEN;
+
+/// 排行榜记录
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Entry {
+    pub score: u16,
+    /// 关卡序号（从 0 开始）
+    pub level: u8,
+    pub difficulty: Difficulty,
+}
+
+impl Entry {
+    /// 编码为一条带 CRC 的记录
+    pub fn encode(&self) -> [u8; RECORD_LEN] {
+        let score = self.score.to_le_bytes();
+        let payload = [score[0], score[1], self.level, difficulty_to_u8(self.difficulty)];
+        let crc = crc16(&payload).to_le_bytes();
+        [payload[0], payload[1], payload[2], payload[3], crc[0], crc[1]]
+    }
+
+    /// 解码一条记录，未写入或校验失败时返回 None
+    pub fn decode(record: &[u8; RECORD_LEN]) -> Option<Self> {
+        if record.iter().all(|b| *b == 0xFF) {
+            return None;
+        }
+
+        let crc = u16::from_le_bytes([record[4], record[5]]);
+        if crc16(&record[..4]) != crc {
+            return None;
+        }
+
+        Some(Self {
+            score: u16::from_le_bytes([record[0], record[1]]),
+            level: record[2],
+            difficulty: difficulty_from_u8(record[3])?,
+        })
+    }
+}
+
+/// 最高分排行榜（按分数从高到低排列）
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct HighScores {
+    entries: Vec<Entry>,
+}
+
+impl HighScores {
+    /// 创建空排行榜
+    pub fn new() -> Self {
+        Self { entries: Vec::with_capacity(TABLE_LEN) }
+    }
+
+    /// 所有记录（从高到低）
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// 最高分
+    pub fn best(&self) -> Option<u16> {
+        self.entries.first().map(|e| e.score)
+    }
+
+    /// 分数是否能进入排行榜
+    pub fn qualifies(&self, score: u16) -> bool {
+        score > 0
+            && (self.entries.len() < TABLE_LEN
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// 插入记录，返回名次（从 0 开始），未进榜返回 None
+    ///
+    /// 同分时先达到的排在前面
+    pub fn insert(&mut self, entry: Entry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+
+        let rank = self.entries.iter().position(|e| entry.score > e.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(TABLE_LEN);
+        Some(rank)
+    }
+
+    /// 编码整个排行榜，空位填充 0xFF（与擦除后的 flash 相同）
+    pub fn encode(&self) -> [u8; TABLE_BYTES] {
+        let mut bytes = [0xFF; TABLE_BYTES];
+        for (chunk, entry) in bytes.chunks_exact_mut(RECORD_LEN).zip(&self.entries) {
+            chunk.copy_from_slice(&entry.encode());
+        }
+        bytes
+    }
+
+    /// 解码排行榜，跳过损坏的记录
+    pub fn decode(bytes: &[u8; TABLE_BYTES]) -> Self {
+        let mut table = Self::new();
+        for chunk in bytes.chunks_exact(RECORD_LEN) {
+            let record: &[u8; RECORD_LEN] = chunk.try_into().unwrap();
+            if let Some(entry) = Entry::decode(record) {
+                table.insert(entry);
+            }
+        }
+        table
+    }
+
+    /// 从存储中读取排行榜
+    pub fn load<S: ReadStorage>(storage: &mut S) -> Result<Self, S::Error> {
+        let mut bytes = [0; TABLE_BYTES];
+        storage.read(0, &mut bytes)?;
+        Ok(Self::decode(&bytes))
+    }
+
+    /// 把排行榜写入存储
+    pub fn save<S: Storage>(&self, storage: &mut S) -> Result<(), S::Error> {
+        storage.write(0, &self.encode())
+    }
+}
+
+/// 内存存储（主机测试用），初始内容与擦除后的 flash 相同（全 0xFF）
+#[derive(Clone, PartialEq, Debug)]
+pub struct RamStorage {
+    pub data: Vec<u8>,
+}
+
+impl RamStorage {
+    /// 创建指定大小的内存存储
+    pub fn new(capacity: usize) -> Self {
+        Self { data: alloc::vec![0xFF; capacity] }
+    }
+}
+
+/// 内存存储越界访问
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct OutOfBounds;
+
+impl ReadStorage for RamStorage {
+    type Error = OutOfBounds;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        let start = offset as usize;
+        let src = self.data.get(start..start + bytes.len()).ok_or(OutOfBounds)?;
+        bytes.copy_from_slice(src);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        self.data.len()
+    }
+}
+
+impl Storage for RamStorage {
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        let start = offset as usize;
+        let dst = self.data.get_mut(start..start + bytes.len()).ok_or(OutOfBounds)?;
+        dst.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// CRC-16/CCITT-FALSE（多项式 0x1021，初始值 0xFFFF）
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+fn difficulty_to_u8(difficulty: Difficulty) -> u8 {
+    match difficulty {
+        Difficulty::Easy => 0,
+        Difficulty::Normal => 1,
+        Difficulty::Hard => 2,
+    }
+}
+
+fn difficulty_from_u8(value: u8) -> Option<Difficulty> {
+    match value {
+        0 => Some(Difficulty::Easy),
+        1 => Some(Difficulty::Normal),
+        2 => Some(Difficulty::Hard),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u16) -> Entry {
+        Entry { score, level: 0, difficulty: Difficulty::Normal }
+    }
+
+    fn scores(table: &HighScores) -> Vec<u16> {
+        table.entries().iter().map(|e| e.score).collect()
+    }
+
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn keeps_top_entries_sorted() {
+        let mut table = HighScores::new();
+        for score in [3, 9, 1, 7, 5, 8, 2] {
+            table.insert(entry(score));
+        }
+        assert_eq!(scores(&table), [9, 8, 7, 5, 3]);
+        assert_eq!(table.best(), Some(9));
+    }
+
+    #[test]
+    fn insert_reports_rank() {
+        let mut table = HighScores::new();
+        assert_eq!(table.insert(entry(5)), Some(0));
+        assert_eq!(table.insert(entry(10)), Some(0));
+        assert_eq!(table.insert(entry(5)), Some(2));
+        assert_eq!(table.insert(entry(0)), None);
+    }
+
+    #[test]
+    fn full_table_rejects_low_scores() {
+        let mut table = HighScores::new();
+        for score in [10, 20, 30, 40, 50] {
+            table.insert(entry(score));
+        }
+        assert!(!table.qualifies(10));
+        assert_eq!(table.insert(entry(10)), None);
+        assert_eq!(table.insert(entry(11)), Some(4));
+        assert_eq!(scores(&table), [50, 40, 30, 20, 11]);
+    }
+
+    #[test]
+    fn record_roundtrip() {
+        let e = Entry { score: 0x1234, level: 3, difficulty: Difficulty::Hard };
+        assert_eq!(Entry::decode(&e.encode()), Some(e));
+    }
+
+    #[test]
+    fn table_roundtrip_through_storage() {
+        let mut table = HighScores::new();
+        table.insert(Entry { score: 42, level: 2, difficulty: Difficulty::Easy });
+        table.insert(Entry { score: 7, level: 0, difficulty: Difficulty::Hard });
+
+        let mut storage = RamStorage::new(4096);
+        table.save(&mut storage).unwrap();
+        assert_eq!(HighScores::load(&mut storage).unwrap(), table);
+    }
+
+    #[test]
+    fn erased_flash_loads_empty_table() {
+        let mut storage = RamStorage::new(4096);
+        assert!(HighScores::load(&mut storage).unwrap().entries().is_empty());
+    }
+
+    #[test]
+    fn corrupted_record_is_dropped() {
+        let mut table = HighScores::new();
+        for score in [10, 20, 30] {
+            table.insert(entry(score));
+        }
+        let mut storage = RamStorage::new(4096);
+        table.save(&mut storage).unwrap();
+
+        // 第二条记录（20 分）的分数被翻转了一位
+        storage.data[RECORD_LEN] ^= 0x01;
+        let loaded = HighScores::load(&mut storage).unwrap();
+        assert_eq!(scores(&loaded), [30, 10]);
+
+        // 重新保存后损坏的记录被清除
+        loaded.save(&mut storage).unwrap();
+        assert_eq!(&storage.data[2 * RECORD_LEN..3 * RECORD_LEN], &[0xFF; RECORD_LEN]);
+        assert_eq!(HighScores::load(&mut storage).unwrap(), loaded);
+    }
+
+    #[test]
+    fn corrupted_crc_and_unknown_difficulty_are_dropped() {
+        let mut bytes = HighScores::new().encode();
+        bytes[..RECORD_LEN].copy_from_slice(&entry(99).encode());
+        bytes[RECORD_LEN..2 * RECORD_LEN].copy_from_slice(&entry(50).encode());
+        bytes[RECORD_LEN + 5] ^= 0x80;
+
+        // 难度字段无效但 CRC 正确
+        let payload = [1, 0, 0, 9];
+        let crc = crc16(&payload).to_le_bytes();
+        bytes[2 * RECORD_LEN..3 * RECORD_LEN].copy_from_slice(&[1, 0, 0, 9, crc[0], crc[1]]);
+
+        assert_eq!(scores(&HighScores::decode(&bytes)), [99]);
+    }
+
+    #[test]
+    fn out_of_order_records_are_sorted_on_load() {
+        let mut bytes = HighScores::new().encode();
+        for (i, score) in [4, 12, 8].into_iter().enumerate() {
+            bytes[i * RECORD_LEN..(i + 1) * RECORD_LEN].copy_from_slice(&entry(score).encode());
+        }
+        assert_eq!(scores(&HighScores::decode(&bytes)), [12, 8, 4]);
+    }
+
+    #[test]
+    fn storage_too_small_reports_error() {
+        let mut storage = RamStorage::new(TABLE_BYTES - 1);
+        assert_eq!(HighScores::load(&mut storage), Err(OutOfBounds));
+        assert_eq!(HighScores::new().save(&mut storage), Err(OutOfBounds));
+    }
+}
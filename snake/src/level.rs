@@ -0,0 +1,129 @@
+//! 关卡（障碍物布局）模块
+//!
+//! 关卡使用 ASCII 地图描述，每行一行格子：`#` 为墙，其他字符为空地。
+//! 地图从棋盘左上角开始放置，超出棋盘的部分忽略，未覆盖的部分为空地。
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::snake::{GameConfig, Position};
+
+/// 内置关卡（按 24x24 棋盘设计）
+pub const LEVELS: [&str; 4] = [
+    include_str!("levels/level1.txt"),
+    include_str!("levels/level2.txt"),
+    include_str!("levels/level3.txt"),
+    include_str!("levels/level4.txt"),
+];
+
+/// 每关需要的分数（所有玩家合计），达到后进入下一关
+///
+/// 奖励食物按 `FoodKind::points` 计分，所以会让关卡前进得更快
+pub const LEVEL_UP_SCORE: u16 = 5;
+
+/// 墙
+pub const WALL: char = '#';
+
+/// 关卡障碍物
+#[derive(Clone, PartialEq, Debug)]
+pub struct Level {
+    cols: u16,
+    rows: u16,
+    walls: Vec<bool>,
+}
+
+impl Level {
+    /// 创建没有障碍物的关卡
+    pub fn empty(config: &GameConfig) -> Self {
+        Self {
+            cols: config.cols,
+            rows: config.rows,
+            walls: vec![false; config.cols as usize * config.rows as usize],
+        }
+    }
+
+    /// 从 ASCII 地图解析关卡
+    pub fn parse(map: &str, config: &GameConfig) -> Self {
+        let mut level = Self::empty(config);
+        for (y, line) in map.lines().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                if ch == WALL && x < level.cols as usize && y < level.rows as usize {
+                    level.set_wall(Position { x: x as u16, y: y as u16 }, true);
+                }
+            }
+        }
+        level
+    }
+
+    /// 是否为墙（棋盘外不算墙，由边界模式处理）
+    pub fn is_wall(&self, pos: Position) -> bool {
+        self.index(pos).is_some_and(|i| self.walls[i])
+    }
+
+    /// 设置或清除墙
+    pub fn set_wall(&mut self, pos: Position, wall: bool) {
+        if let Some(i) = self.index(pos) {
+            self.walls[i] = wall;
+        }
+    }
+
+    /// 遍历所有墙的位置
+    pub fn walls(&self) -> impl Iterator<Item = Position> + '_ {
+        let cols = self.cols as usize;
+        self.walls
+            .iter()
+            .enumerate()
+            .filter(|(_, wall)| **wall)
+            .map(move |(i, _)| Position { x: (i % cols) as u16, y: (i / cols) as u16 })
+    }
+
+    fn index(&self, pos: Position) -> Option<usize> {
+        if pos.x < self.cols && pos.y < self.rows {
+            Some(pos.y as usize * self.cols as usize + pos.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn parses_walls() {
+        let level = Level::parse("#..\n.#.\n", &GameConfig::new(4, 3, 10));
+        assert!(level.is_wall(pos(0, 0)));
+        assert!(level.is_wall(pos(1, 1)));
+        assert!(!level.is_wall(pos(2, 0)));
+        assert!(!level.is_wall(pos(3, 2)));
+        assert_eq!(level.walls().count(), 2);
+    }
+
+    #[test]
+    fn ignores_map_outside_board() {
+        let level = Level::parse("....#\n#####\n#", &GameConfig::new(4, 2, 10));
+        assert_eq!(level.walls().count(), 4);
+        assert!(!level.is_wall(pos(4, 0)));
+        assert!(!level.is_wall(pos(0, 2)));
+    }
+
+    #[test]
+    fn builtin_levels_fit_default_board() {
+        let config = GameConfig::default();
+        for map in LEVELS {
+            assert_eq!(map.lines().count(), config.rows as usize);
+            assert!(map.lines().all(|line| line.len() == config.cols as usize));
+
+            // 初始的蛇所在位置必须是空地
+            let level = Level::parse(map, &config);
+            for x in 0..3 {
+                assert!(!level.is_wall(pos(config.cols / 2 - x, config.rows / 2)));
+            }
+        }
+    }
+}
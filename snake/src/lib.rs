@@ -1,5 +1,22 @@
-#![no_std]
+//! 贪吃蛇游戏库
+//!
+//! 游戏逻辑不依赖硬件，可以在主机上运行单元测试：
+//!
+//! ```text
+//! cargo +stable test --lib --target x86_64-unknown-linux-gnu
+//! ```
+
+#![cfg_attr(not(test), no_std)]
 
 extern crate alloc;
 
+pub mod autopilot;
+pub mod bdf;
+pub mod body;
+pub mod food;
+pub mod highscore;
+pub mod level;
+pub mod render;
+pub mod replay;
 pub mod snake;
+pub mod sprite;
@@ -0,0 +1,687 @@
+//! 贪吃蛇画面绘制模块
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use embedded_graphics::{
+    Drawable,
+    mono_font::{
+        MonoFont, MonoTextStyleBuilder,
+        ascii::{FONT_10X20, FONT_6X10},
+    },
+    pixelcolor::Rgb565,
+    prelude::{DrawTarget, DrawTargetExt, Point, Primitive, RgbColor, Size},
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use crate::food::FoodKind;
+use crate::highscore::HighScores;
+use crate::snake::{Game, GameState, MatchResult, Position};
+
+/// 蛇身颜色（深绿色）
+pub const BODY_COLOR: Rgb565 = Rgb565::new(0, 200 >> 3, 0);
+
+/// 第二条蛇的蛇头颜色（青色）
+pub const PLAYER2_HEAD_COLOR: Rgb565 = Rgb565::CYAN;
+
+/// 第二条蛇的蛇身颜色（深青色）
+pub const PLAYER2_BODY_COLOR: Rgb565 = Rgb565::new(0, 100 >> 2, 200 >> 3);
+
+/// 加速食物颜色（橙色）
+pub const SPEED_UP_COLOR: Rgb565 = Rgb565::new(31, 32, 0);
+
+/// 障碍物颜色（灰色）
+pub const WALL_COLOR: Rgb565 = Rgb565::new(16, 32, 16);
+
+/// 食物颜色：普通红色，奖励黄色，缩短紫色，加速橙色
+pub fn food_color(kind: FoodKind) -> Rgb565 {
+    match kind {
+        FoodKind::Normal => Rgb565::RED,
+        FoodKind::Bonus => Rgb565::YELLOW,
+        FoodKind::Shrink => Rgb565::MAGENTA,
+        FoodKind::SpeedUp => SPEED_UP_COLOR,
+    }
+}
+
+/// 计算格子对应的像素区域
+pub fn cell_rect(game: &Game, pos: Position) -> Rectangle {
+    let cell = game.config.cell_px;
+    Rectangle::new(
+        Point::new((pos.x * cell) as i32, (pos.y * cell) as i32),
+        Size::new(cell as u32, cell as u32),
+    )
+}
+
+/// 绘制游戏画面
+///
+/// 只绘制棋盘区域（`cols * cell_px` x `rows * cell_px`），左上角对齐
+pub fn draw_game<D>(target: &mut D, game: &Game) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let board = Rectangle::new(
+        Point::zero(),
+        Size::new(game.config.width_px(), game.config.height_px()),
+    );
+
+    // 清空棋盘
+    board.into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK)).draw(target)?;
+
+    // 绘制障碍物
+    for pos in game.walls.walls() {
+        cell_rect(game, pos).into_styled(PrimitiveStyle::with_fill(WALL_COLOR)).draw(target)?;
+    }
+
+    // 绘制蛇
+    for (i, pos) in game.snake.iter().enumerate() {
+        let color = if i == 0 {
+            Rgb565::GREEN  // 蛇头
+        } else {
+            BODY_COLOR
+        };
+        cell_rect(game, *pos).into_styled(PrimitiveStyle::with_fill(color)).draw(target)?;
+    }
+
+    // 绘制第二条蛇（双人模式）
+    if let Some(p2) = &game.player2 {
+        for (i, pos) in p2.snake.iter().enumerate() {
+            let color = if i == 0 { PLAYER2_HEAD_COLOR } else { PLAYER2_BODY_COLOR };
+            cell_rect(game, *pos).into_styled(PrimitiveStyle::with_fill(color)).draw(target)?;
+        }
+    }
+
+    // 绘制食物
+    cell_rect(game, game.food).into_styled(PrimitiveStyle::with_fill(food_color(game.food_kind))).draw(target)?;
+
+    // 绘制边框
+    board.into_styled(PrimitiveStyle::with_stroke(Rgb565::WHITE, 1)).draw(target)?;
+
+    Ok(())
+}
+
+/// 格子内容（增量绘制时用来比较前后两帧）
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Cell {
+    Empty,
+    Wall,
+    Head,
+    Body,
+    Head2,
+    Body2,
+    Food(FoodKind),
+}
+
+impl Cell {
+    fn color(self) -> Rgb565 {
+        match self {
+            Cell::Empty => Rgb565::BLACK,
+            Cell::Wall => WALL_COLOR,
+            Cell::Head => Rgb565::GREEN,
+            Cell::Body => BODY_COLOR,
+            Cell::Head2 => PLAYER2_HEAD_COLOR,
+            Cell::Body2 => PLAYER2_BODY_COLOR,
+            Cell::Food(kind) => food_color(kind),
+        }
+    }
+}
+
+/// 上一帧每个格子的内容（`DirtyRenderer` 和 `SpriteRenderer` 共用的逐格比较）
+///
+/// 状态切换或棋盘大小变化时需要整屏重绘；游戏进行中找出变化的格子；
+/// 其他状态下画面不变，什么都不用画。
+pub(crate) struct CellCache<T> {
+    cells: Vec<T>,
+    state: Option<GameState>,
+}
+
+impl<T> Default for CellCache<T> {
+    fn default() -> Self {
+        Self { cells: Vec::new(), state: None }
+    }
+}
+
+impl<T: Copy + PartialEq> CellCache<T> {
+    /// 下次比较时要求整屏重绘
+    pub(crate) fn invalidate(&mut self) {
+        self.state = None;
+    }
+
+    /// 记录新的一帧（按行排列），返回 None 表示需要整屏重绘，否则返回变化的格子
+    pub(crate) fn update(&mut self, game: &Game, cells: Vec<T>) -> Option<Vec<Position>> {
+        if self.state != Some(game.state) || self.cells.len() != cells.len() {
+            self.cells = cells;
+            self.state = Some(game.state);
+            return None;
+        }
+
+        let mut changed = Vec::new();
+        if game.state == GameState::Playing {
+            let cols = game.config.cols as usize;
+            for (i, (old, new)) in self.cells.iter().zip(&cells).enumerate() {
+                if old != new {
+                    changed.push(Position { x: (i % cols) as u16, y: (i / cols) as u16 });
+                }
+            }
+            self.cells = cells;
+        }
+        Some(changed)
+    }
+
+    /// 记录的每个格子及其位置
+    pub(crate) fn iter<'a>(&'a self, game: &Game) -> impl Iterator<Item = (Position, T)> + 'a {
+        let cols = game.config.cols as usize;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (Position { x: (i % cols) as u16, y: (i / cols) as u16 }, *cell))
+    }
+
+    /// 记录的格子内容
+    pub(crate) fn get(&self, game: &Game, pos: Position) -> T {
+        self.cells[pos.y as usize * game.config.cols as usize + pos.x as usize]
+    }
+}
+
+/// 增量绘制器
+///
+/// 记住上一帧每个格子的内容，游戏进行中只重绘变化的格子（新蛇头、
+/// 旧蛇头变成蛇身、离开的蛇尾、新食物），直接写到 `DrawTarget`，
+/// 不需要全屏帧缓冲区。状态切换（开始、暂停、结束画面）时整屏重绘。
+#[derive(Default)]
+pub struct DirtyRenderer {
+    cells: CellCache<Cell>,
+}
+
+impl DirtyRenderer {
+    /// 创建绘制器，第一次绘制为整屏重绘
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 下次绘制时整屏重绘（例如屏幕被其他内容覆盖后）
+    pub fn invalidate(&mut self) {
+        self.cells.invalidate();
+    }
+
+    /// 绘制一帧，返回是否进行了整屏重绘
+    ///
+    /// 非游戏状态下画面不变，只在进入该状态时绘制一次
+    pub fn draw<D>(&mut self, target: &mut D, game: &Game) -> Result<bool, D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let Some(changed) = self.cells.update(game, Self::cell_map(game)) else {
+            draw_game(target, game)?;
+            draw_overlay(target, game)?;
+            return Ok(true);
+        };
+
+        for pos in changed {
+            Self::draw_cell(target, game, pos, self.cells.get(game, pos))?;
+        }
+
+        Ok(false)
+    }
+
+    /// 当前帧每个格子的内容
+    fn cell_map(game: &Game) -> Vec<Cell> {
+        let cols = game.config.cols as usize;
+        let mut cells = vec![Cell::Empty; cols * game.config.rows as usize];
+        let mut set = |pos: Position, cell: Cell| {
+            if let Some(slot) = cells.get_mut(pos.y as usize * cols + pos.x as usize) {
+                *slot = cell;
+            }
+        };
+
+        for pos in game.walls.walls() {
+            set(pos, Cell::Wall);
+        }
+        for (i, pos) in game.snake.iter().enumerate() {
+            set(*pos, if i == 0 { Cell::Head } else { Cell::Body });
+        }
+        if let Some(p2) = &game.player2 {
+            for (i, pos) in p2.snake.iter().enumerate() {
+                set(*pos, if i == 0 { Cell::Head2 } else { Cell::Body2 });
+            }
+        }
+        set(game.food, Cell::Food(game.food_kind));
+        cells
+    }
+
+    /// 重绘一个格子，落在边缘的格子补画被覆盖的边框
+    fn draw_cell<D>(target: &mut D, game: &Game, pos: Position, cell: Cell) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_solid(&cell_rect(game, pos), cell.color())?;
+        restore_border(target, game, pos)
+    }
+}
+
+/// 补画被格子覆盖的那段边框（只有落在棋盘边缘的格子需要）
+pub(crate) fn restore_border<D>(target: &mut D, game: &Game, pos: Position) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    if pos.x == 0 || pos.y == 0 || pos.x == game.config.cols - 1 || pos.y == game.config.rows - 1 {
+        let board = Rectangle::new(
+            Point::zero(),
+            Size::new(game.config.width_px(), game.config.height_px()),
+        );
+        board
+            .into_styled(PrimitiveStyle::with_stroke(Rgb565::WHITE, 1))
+            .draw(&mut target.clipped(&cell_rect(game, pos)))?;
+    }
+
+    Ok(())
+}
+
+/// 绘制状态叠加层（开始画面、暂停、游戏结束、获胜）
+///
+/// 文字居中显示在棋盘上，`Playing` 状态不绘制任何内容
+pub fn draw_overlay<D>(target: &mut D, game: &Game) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let center = Point::new(
+        game.config.width_px() as i32 / 2,
+        game.config.height_px() as i32 / 2,
+    );
+
+    match game.state {
+        GameState::Playing => {}
+        GameState::Title => {
+            draw_centered(target, "SNAKE", &FONT_10X20, Rgb565::GREEN, center - Point::new(0, 12))?;
+            draw_centered(target, "Press Z to start", &FONT_6X10, Rgb565::WHITE, center + Point::new(0, 12))?;
+        }
+        GameState::Paused => {
+            draw_centered(target, "PAUSED", &FONT_10X20, Rgb565::YELLOW, center)?;
+        }
+        GameState::GameOver | GameState::Cleared if game.player2.is_some() => {
+            let (title, color) = match game.result {
+                Some(MatchResult::Player1Wins) => ("P1 WINS", Rgb565::GREEN),
+                Some(MatchResult::Player2Wins) => ("P2 WINS", PLAYER2_HEAD_COLOR),
+                _ => ("DRAW", Rgb565::YELLOW),
+            };
+            let p2_score = game.player2.as_ref().map_or(0, |p| p.score);
+            let score = alloc::format!("P1 {} : {} P2", game.score, p2_score);
+
+            draw_centered(target, title, &FONT_10X20, color, center - Point::new(0, 20))?;
+            draw_centered(target, &score, &FONT_6X10, Rgb565::WHITE, center)?;
+            draw_centered(target, "Press Z", &FONT_6X10, Rgb565::WHITE, center + Point::new(0, 16))?;
+        }
+        GameState::GameOver | GameState::Cleared => {
+            let score = alloc::format!("Score: {}", game.score);
+            let (title, color) = match game.state {
+                GameState::Cleared => ("YOU WIN!", Rgb565::GREEN),
+                _ => ("GAME OVER", Rgb565::RED),
+            };
+
+            draw_centered(target, title, &FONT_10X20, color, center - Point::new(0, 20))?;
+            draw_centered(target, &score, &FONT_6X10, Rgb565::WHITE, center)?;
+            draw_centered(target, "Press Z", &FONT_6X10, Rgb565::WHITE, center + Point::new(0, 16))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 在开始画面下方绘制排行榜
+pub fn draw_high_scores<D>(target: &mut D, game: &Game, scores: &HighScores) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let mut line = Point::new(
+        game.config.width_px() as i32 / 2,
+        game.config.height_px() as i32 / 2 + 36,
+    );
+
+    for (i, entry) in scores.entries().iter().enumerate() {
+        let text = alloc::format!("{}. {:>5}  L{}", i + 1, entry.score, entry.level + 1);
+        draw_centered(target, &text, &FONT_6X10, Rgb565::CYAN, line)?;
+        line.y += 12;
+    }
+
+    Ok(())
+}
+
+/// 在指定中心点绘制一行文字（黑色背景，保证在蛇身上也能看清）
+fn draw_centered<D>(
+    target: &mut D,
+    text: &str,
+    font: &MonoFont,
+    color: Rgb565,
+    center: Point,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let character_style = MonoTextStyleBuilder::new()
+        .font(font)
+        .text_color(color)
+        .background_color(Rgb565::BLACK)
+        .build();
+    let text_style = TextStyleBuilder::new()
+        .alignment(Alignment::Center)
+        .baseline(Baseline::Middle)
+        .build();
+    Text::with_text_style(text, center, character_style, text_style).draw(target)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Body;
+    use crate::snake::{BoardMode, GameConfig};
+    use alloc::boxed::Box;
+    use embedded_graphics::{geometry::Dimensions, mock_display::MockDisplay, prelude::PointsIter};
+
+    fn render(game: &Game) -> MockDisplay<Rgb565> {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        draw_game(&mut display, game).unwrap();
+        display
+    }
+
+    #[test]
+    fn non_square_board_uses_cell_size() {
+        // 6x4 格子，每格 4 像素 => 24x16 像素
+        let mut game = Game::with_config(GameConfig::new(6, 4, 4), BoardMode::Walls, 1);
+        game.food = Position { x: 5, y: 3 };
+        let display = render(&game);
+
+        // 蛇头在 (3,2)，内部像素
+        assert_eq!(display.get_pixel(Point::new(3 * 4 + 1, 2 * 4 + 1)), Some(Rgb565::GREEN));
+        assert_eq!(display.get_pixel(Point::new(2 * 4 + 1, 2 * 4 + 1)), Some(BODY_COLOR));
+        assert_eq!(display.get_pixel(Point::new(5 * 4 + 1, 3 * 4 + 1)), Some(Rgb565::RED));
+
+        // 边框
+        assert_eq!(display.get_pixel(Point::new(23, 5)), Some(Rgb565::WHITE));
+        assert_eq!(display.get_pixel(Point::new(5, 15)), Some(Rgb565::WHITE));
+
+        // 棋盘之外不绘制
+        assert_eq!(display.get_pixel(Point::new(24, 5)), None);
+        assert_eq!(display.get_pixel(Point::new(5, 16)), None);
+    }
+
+    #[test]
+    fn cell_rect_scales_with_config() {
+        let game = Game::with_config(GameConfig::new(16, 16, 8), BoardMode::Walls, 1);
+        let rect = cell_rect(&game, Position { x: 3, y: 5 });
+        assert_eq!(rect, Rectangle::new(Point::new(24, 40), Size::new(8, 8)));
+    }
+
+    #[test]
+    fn draws_walls() {
+        let mut game = Game::with_config(GameConfig::new(6, 4, 4), BoardMode::Walls, 1);
+        game.food = Position { x: 5, y: 3 };
+        game.walls.set_wall(Position { x: 1, y: 0 }, true);
+        let display = render(&game);
+        assert_eq!(display.get_pixel(Point::new(4 + 1, 1)), Some(WALL_COLOR));
+        assert_eq!(display.get_pixel(Point::new(2 * 4 + 1, 1)), Some(Rgb565::BLACK));
+    }
+
+    fn render_overlay(game: &Game) -> MockDisplay<Rgb565> {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        draw_overlay(&mut display, game).unwrap();
+        display
+    }
+
+    fn count_color(display: &MockDisplay<Rgb565>, color: Rgb565) -> usize {
+        display.bounding_box().points().filter(|p| display.get_pixel(*p) == Some(color)).count()
+    }
+
+    #[test]
+    fn playing_has_no_overlay() {
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        game.press_button();
+        let display = render_overlay(&game);
+        assert_eq!(display.affected_area(), Rectangle::zero());
+    }
+
+    #[test]
+    fn title_and_pause_overlays() {
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        assert!(count_color(&render_overlay(&game), Rgb565::GREEN) > 0);
+
+        game.press_button();
+        game.press_button();
+        assert_eq!(game.state, GameState::Paused);
+        let display = render_overlay(&game);
+        assert!(count_color(&display, Rgb565::YELLOW) > 0);
+        assert_eq!(count_color(&display, Rgb565::GREEN), 0);
+    }
+
+    #[test]
+    fn game_over_overlay_shows_score() {
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        game.state = GameState::GameOver;
+        let zero = render_overlay(&game);
+        assert!(count_color(&zero, Rgb565::RED) > 0);
+
+        game.score = 42;
+        let forty_two = render_overlay(&game);
+        assert_ne!(zero, forty_two);
+        assert_eq!(count_color(&zero, Rgb565::RED), count_color(&forty_two, Rgb565::RED));
+    }
+
+    #[test]
+    fn cleared_board_shows_win() {
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        game.state = GameState::Cleared;
+        let display = render_overlay(&game);
+        assert!(count_color(&display, Rgb565::GREEN) > 0);
+        assert_eq!(count_color(&display, Rgb565::RED), 0);
+    }
+
+    #[test]
+    fn two_player_game_over_shows_winner() {
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        game.set_two_player(true);
+        game.state = GameState::GameOver;
+
+        game.result = Some(MatchResult::Player2Wins);
+        let p2_wins = render_overlay(&game);
+        assert!(count_color(&p2_wins, PLAYER2_HEAD_COLOR) > 0);
+        assert_eq!(count_color(&p2_wins, Rgb565::RED), 0);
+
+        game.result = Some(MatchResult::Draw);
+        assert!(count_color(&render_overlay(&game), Rgb565::YELLOW) > 0);
+    }
+
+    #[test]
+    fn second_snake_is_drawn() {
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        game.set_two_player(true);
+        let display = render(&game);
+        let head = game.player2.as_ref().unwrap().snake[0];
+        let point = Point::new(head.x as i32 * 4 + 1, head.y as i32 * 4 + 1);
+        assert_eq!(display.get_pixel(point), Some(PLAYER2_HEAD_COLOR));
+    }
+
+    #[test]
+    fn food_kinds_have_distinct_colors() {
+        let kinds = [FoodKind::Normal, FoodKind::Bonus, FoodKind::Shrink, FoodKind::SpeedUp];
+        for (i, a) in kinds.iter().enumerate() {
+            for b in &kinds[i + 1..] {
+                assert_ne!(food_color(*a), food_color(*b));
+            }
+        }
+
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        game.food_kind = FoodKind::Bonus;
+        let display = render(&game);
+        let point = Point::new(game.food.x as i32 * 4 + 1, game.food.y as i32 * 4 + 1);
+        assert_eq!(display.get_pixel(point), Some(Rgb565::YELLOW));
+    }
+
+    #[test]
+    fn high_scores_are_listed() {
+        use crate::highscore::Entry;
+        use crate::snake::Difficulty;
+
+        let game = Game::with_config(GameConfig::new(16, 8, 4), BoardMode::Walls, 1);
+        let mut scores = HighScores::new();
+        let mut display = MockDisplay::new();
+        display.set_allow_out_of_bounds_drawing(true);
+        draw_high_scores(&mut display, &game, &scores).unwrap();
+        assert_eq!(display.affected_area(), Rectangle::zero());
+
+        scores.insert(Entry { score: 3, level: 0, difficulty: Difficulty::Normal });
+        draw_high_scores(&mut display, &game, &scores).unwrap();
+        assert!(count_color(&display, Rgb565::CYAN) > 0);
+    }
+
+    /// 记录所有写入操作的绘制目标
+    #[derive(Default)]
+    struct Recorder {
+        fills: Vec<(Rectangle, Rgb565)>,
+        pixels: usize,
+    }
+
+    impl Dimensions for Recorder {
+        fn bounding_box(&self) -> Rectangle {
+            Rectangle::new(Point::zero(), Size::new(240, 240))
+        }
+    }
+
+    impl DrawTarget for Recorder {
+        type Color = Rgb565;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = embedded_graphics::Pixel<Rgb565>>,
+        {
+            self.pixels += pixels.into_iter().count();
+            Ok(())
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, color: Rgb565) -> Result<(), Self::Error> {
+            self.fills.push((*area, color));
+            self.pixels += area.size.width as usize * area.size.height as usize;
+            Ok(())
+        }
+    }
+
+    fn playing_game() -> Box<Game> {
+        let mut game = Game::with_seed(1);
+        game.press_button();
+        game.food = Position { x: 0, y: 5 };
+        game
+    }
+
+    fn rect(x: i32, y: i32) -> Rectangle {
+        Rectangle::new(Point::new(x * 10, y * 10), Size::new(10, 10))
+    }
+
+    #[test]
+    fn first_frame_is_full_redraw() {
+        let game = playing_game();
+        let mut renderer = DirtyRenderer::new();
+        let mut target = Recorder::default();
+        assert!(renderer.draw(&mut target, &game).unwrap());
+        assert!(target.pixels >= 240 * 240);
+    }
+
+    #[test]
+    fn move_redraws_head_neck_and_tail_only() {
+        let mut game = playing_game();
+        let mut renderer = DirtyRenderer::new();
+        renderer.draw(&mut Recorder::default(), &game).unwrap();
+
+        game.update();
+        let mut target = Recorder::default();
+        assert!(!renderer.draw(&mut target, &game).unwrap());
+
+        // 蛇从 (12..10, 12) 移动到 (13..11, 12)
+        assert_eq!(target.fills, vec![
+            (rect(10, 12), Rgb565::BLACK),
+            (rect(12, 12), BODY_COLOR),
+            (rect(13, 12), Rgb565::GREEN),
+        ]);
+        assert_eq!(target.pixels, 3 * 10 * 10);
+    }
+
+    #[test]
+    fn eating_redraws_head_neck_and_new_food() {
+        let mut game = playing_game();
+        game.food = Position { x: 13, y: 12 };
+        let mut renderer = DirtyRenderer::new();
+        renderer.draw(&mut Recorder::default(), &game).unwrap();
+
+        game.update();
+        let mut target = Recorder::default();
+        renderer.draw(&mut target, &game).unwrap();
+
+        let food = game.food;
+        let mut expected = vec![
+            (rect(12, 12), BODY_COLOR),
+            (rect(13, 12), Rgb565::GREEN),
+            (rect(food.x as i32, food.y as i32), Rgb565::RED),
+        ];
+        expected.sort_by_key(|(r, _)| (r.top_left.y, r.top_left.x));
+        assert_eq!(target.fills, expected);
+    }
+
+    #[test]
+    fn unchanged_frame_writes_nothing() {
+        let mut game = playing_game();
+        let mut renderer = DirtyRenderer::new();
+        renderer.draw(&mut Recorder::default(), &game).unwrap();
+
+        let mut target = Recorder::default();
+        renderer.draw(&mut target, &game).unwrap();
+        assert_eq!(target.pixels, 0);
+
+        // 暂停画面只绘制一次
+        game.press_button();
+        assert!(renderer.draw(&mut Recorder::default(), &game).unwrap());
+        let mut target = Recorder::default();
+        assert!(!renderer.draw(&mut target, &game).unwrap());
+        assert_eq!(target.pixels, 0);
+    }
+
+    #[test]
+    fn edge_cell_restores_border() {
+        let mut game = Game::with_config(GameConfig::new(6, 4, 10), BoardMode::Walls, 1);
+        game.press_button();
+        game.food = Position { x: 0, y: 3 };
+        game.snake = Body::from_cells(&game.config, [Position { x: 3, y: 0 }, Position { x: 2, y: 0 }, Position { x: 1, y: 0 }]);
+        let mut renderer = DirtyRenderer::new();
+        renderer.draw(&mut Recorder::default(), &game).unwrap();
+
+        game.update();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        renderer.draw(&mut display, &game).unwrap();
+
+        // 新蛇头 (4,0) 在上边缘，边框保持白色
+        assert_eq!(display.get_pixel(Point::new(45, 0)), Some(Rgb565::WHITE));
+        assert_eq!(display.get_pixel(Point::new(45, 1)), Some(Rgb565::GREEN));
+        // 离开的蛇尾 (1,0) 也补画了边框
+        assert_eq!(display.get_pixel(Point::new(15, 0)), Some(Rgb565::WHITE));
+        assert_eq!(display.get_pixel(Point::new(15, 1)), Some(Rgb565::BLACK));
+    }
+
+    #[test]
+    fn state_change_forces_full_redraw() {
+        let mut game = playing_game();
+        let mut renderer = DirtyRenderer::new();
+        renderer.draw(&mut Recorder::default(), &game).unwrap();
+
+        // 暂停和继续都会整屏重绘（清除或绘制叠加层）
+        game.press_button();
+        assert!(renderer.draw(&mut Recorder::default(), &game).unwrap());
+        game.press_button();
+        assert!(renderer.draw(&mut Recorder::default(), &game).unwrap());
+        assert!(!renderer.draw(&mut Recorder::default(), &game).unwrap());
+
+        renderer.invalidate();
+        assert!(renderer.draw(&mut Recorder::default(), &game).unwrap());
+    }
+}
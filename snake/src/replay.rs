@@ -0,0 +1,610 @@
+//! 录像模块
+//!
+//! 一局游戏由种子和每个 tick 的输入完全决定，录下这些就能在主机上
+//! 通过 `Game::update` 重放，复现设备上报告的问题。
+//!
+//! 编码格式：
+//!
+//! ```text
+//! 偏移  长度  内容
+//! 0     2     魔数 "SR"
+//! 2     1     版本（1）
+//! 3     1     标志：bit0 穿墙模式，bit1 使用内置关卡，bit2 双人模式，
+//!             bit3 使用内置食物概率表
+//! 4     2     种子（小端）
+//! 6     2     列数（小端）
+//! 8     2     行数（小端）
+//! 10    2     最终分数（小端）
+//! 12    2     最终蛇长（小端）
+//! 14    ...   事件流，每个事件 4 位，高 4 位在前
+//! ```
+//!
+//! 事件：`1..=4` 转向（上、下、左、右），`5..=15` 连续 1..=11 个 tick，
+//! `0` 后跟 `1..=4` 为第二条蛇转向，末尾单独的 `0` 为填充。
+
+use alloc::vec::Vec;
+
+use crate::food::FOOD_TABLE;
+use crate::level::LEVELS;
+use crate::snake::{BoardMode, Direction, Game, GameConfig, GameState};
+
+/// 魔数
+pub const MAGIC: [u8; 2] = *b"SR";
+
+/// 格式版本
+pub const VERSION: u8 = 1;
+
+/// 头部字节数
+pub const HEADER_LEN: usize = 14;
+
+const FLAG_WRAP: u8 = 0b01;
+const FLAG_LEVELS: u8 = 0b10;
+const FLAG_TWO_PLAYER: u8 = 0b100;
+const FLAG_FOOD_TABLE: u8 = 0b1000;
+
+const PLAYER2_PREFIX: u8 = 0;
+
+const TICK_BASE: u8 = 4;
+const MAX_TICK_RUN: u8 = 15 - TICK_BASE;
+
+/// 录像事件
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Event {
+    /// 调用一次 `set_direction`
+    Turn(Direction),
+    /// 调用一次 `set_player2_direction`
+    Turn2(Direction),
+    /// 调用一次 `update`
+    Tick,
+}
+
+/// 重放结果
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Outcome {
+    pub score: u16,
+    pub length: u16,
+    pub state: GameState,
+}
+
+/// 解码错误
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DecodeError {
+    /// 数据太短
+    Truncated,
+    /// 魔数不对
+    BadMagic,
+    /// 不支持的版本
+    UnsupportedVersion(u8),
+}
+
+/// 重放结果与录像不一致
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Mismatch {
+    pub expected_score: u16,
+    pub expected_length: u16,
+    pub actual: Outcome,
+}
+
+/// 一局游戏的录像
+#[derive(Clone, PartialEq, Debug)]
+pub struct Replay {
+    pub seed: u16,
+    pub cols: u16,
+    pub rows: u16,
+    pub mode: BoardMode,
+    /// 是否使用内置关卡 `LEVELS`
+    pub levels: bool,
+    /// 是否为双人模式
+    pub two_player: bool,
+    /// 是否使用内置食物概率表 `FOOD_TABLE`（否则只有普通食物）
+    pub typed_food: bool,
+    pub events: Vec<Event>,
+    /// 录像结束时的分数
+    pub score: u16,
+    /// 录像结束时的蛇长
+    pub length: u16,
+}
+
+impl Replay {
+    /// 编码为字节
+    pub fn encode(&self) -> Vec<u8> {
+        let mut flags = 0;
+        if self.mode == BoardMode::Wrap {
+            flags |= FLAG_WRAP;
+        }
+        if self.levels {
+            flags |= FLAG_LEVELS;
+        }
+        if self.two_player {
+            flags |= FLAG_TWO_PLAYER;
+        }
+        if self.typed_food {
+            flags |= FLAG_FOOD_TABLE;
+        }
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.events.len() / 2 + 1);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.push(flags);
+        for value in [self.seed, self.cols, self.rows, self.score, self.length] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut nibbles = Vec::with_capacity(self.events.len());
+        let mut ticks = 0;
+        for event in &self.events {
+            match event {
+                Event::Tick => {
+                    ticks += 1;
+                    if ticks == MAX_TICK_RUN {
+                        nibbles.push(TICK_BASE + ticks);
+                        ticks = 0;
+                    }
+                }
+                Event::Turn(dir) | Event::Turn2(dir) => {
+                    if ticks > 0 {
+                        nibbles.push(TICK_BASE + ticks);
+                        ticks = 0;
+                    }
+                    if matches!(event, Event::Turn2(_)) {
+                        nibbles.push(PLAYER2_PREFIX);
+                    }
+                    nibbles.push(direction_to_nibble(*dir));
+                }
+            }
+        }
+        if ticks > 0 {
+            nibbles.push(TICK_BASE + ticks);
+        }
+
+        for pair in nibbles.chunks(2) {
+            bytes.push(pair[0] << 4 | pair.get(1).copied().unwrap_or(0));
+        }
+        bytes
+    }
+
+    /// 从字节解码
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(DecodeError::Truncated);
+        }
+        if bytes[0..2] != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        if bytes[2] != VERSION {
+            return Err(DecodeError::UnsupportedVersion(bytes[2]));
+        }
+
+        let flags = bytes[3];
+        let word = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+
+        let mut events = Vec::new();
+        let mut player2 = false;
+        for byte in &bytes[HEADER_LEN..] {
+            for nibble in [byte >> 4, byte & 0x0F] {
+                match nibble {
+                    PLAYER2_PREFIX => player2 = true,
+                    1..=4 if player2 => {
+                        events.push(Event::Turn2(direction_from_nibble(nibble)));
+                        player2 = false;
+                    }
+                    1..=4 => events.push(Event::Turn(direction_from_nibble(nibble))),
+                    _ => {
+                        player2 = false;
+                        for _ in 0..nibble - TICK_BASE {
+                            events.push(Event::Tick);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            seed: word(4),
+            cols: word(6),
+            rows: word(8),
+            mode: if flags & FLAG_WRAP != 0 { BoardMode::Wrap } else { BoardMode::Walls },
+            levels: flags & FLAG_LEVELS != 0,
+            two_player: flags & FLAG_TWO_PLAYER != 0,
+            typed_food: flags & FLAG_FOOD_TABLE != 0,
+            events,
+            score: word(10),
+            length: word(12),
+        })
+    }
+
+    /// 创建与录像开始时相同的游戏（已离开开始画面）
+    ///
+    /// 设备上创建游戏时也要按这个顺序：`set_food_table`、`set_two_player`、
+    /// `set_levels`，后两者都会消耗随机数生成食物
+    pub fn new_game(&self) -> alloc::boxed::Box<Game> {
+        let config = GameConfig::new(self.cols, self.rows, GameConfig::default().cell_px);
+        let mut game = Game::with_config(config, self.mode, self.seed);
+        if self.typed_food {
+            game.set_food_table(&FOOD_TABLE);
+        }
+        if self.two_player {
+            game.set_two_player(true);
+        }
+        if self.levels {
+            game.set_levels(&LEVELS);
+        }
+        game.press_button();
+        game
+    }
+
+    /// 重放整局游戏
+    pub fn run(&self) -> Outcome {
+        let mut game = self.new_game();
+        for event in &self.events {
+            match event {
+                Event::Turn(dir) => game.set_direction(*dir),
+                Event::Turn2(dir) => game.set_player2_direction(*dir),
+                Event::Tick => game.update(),
+            }
+        }
+        outcome(&game)
+    }
+
+    /// 重放并检查最终分数和蛇长是否与录像一致
+    pub fn verify(&self) -> Result<Outcome, Mismatch> {
+        let actual = self.run();
+        if actual.score == self.score && actual.length == self.length {
+            Ok(actual)
+        } else {
+            Err(Mismatch { expected_score: self.score, expected_length: self.length, actual })
+        }
+    }
+}
+
+/// 录像机：代替直接调用 `Game` 的输入方法，同时记录事件
+///
+/// 只记录游戏进行中的输入，开始画面和暂停期间的调用不影响结果
+pub struct Recorder {
+    replay: Replay,
+}
+
+impl Recorder {
+    /// 开始录像，`seed` 必须是创建（或 `reset_with_seed`）该局游戏使用的种子
+    ///
+    /// 头部的模式、关卡、双人和食物表标志都取自 `game`，与游戏保持一致
+    pub fn new(game: &Game, seed: u16) -> Self {
+        Self {
+            replay: Replay {
+                seed,
+                cols: game.config.cols,
+                rows: game.config.rows,
+                mode: game.mode,
+                levels: game.has_levels(),
+                two_player: game.is_two_player(),
+                typed_food: game.food_table() == FOOD_TABLE.as_slice(),
+                events: Vec::new(),
+                score: 0,
+                length: 0,
+            },
+        }
+    }
+
+    /// 转向并记录
+    pub fn set_direction(&mut self, game: &mut Game, dir: Direction) {
+        if game.state == GameState::Playing {
+            self.replay.events.push(Event::Turn(dir));
+        }
+        game.set_direction(dir);
+    }
+
+    /// 第二条蛇转向并记录
+    pub fn set_player2_direction(&mut self, game: &mut Game, dir: Direction) {
+        if game.state == GameState::Playing {
+            self.replay.events.push(Event::Turn2(dir));
+        }
+        game.set_player2_direction(dir);
+    }
+
+    /// 更新游戏并记录
+    pub fn update(&mut self, game: &mut Game) {
+        if game.state == GameState::Playing {
+            self.replay.events.push(Event::Tick);
+        }
+        game.update();
+    }
+
+    /// 已记录的事件数
+    pub fn len(&self) -> usize {
+        self.replay.events.len()
+    }
+
+    /// 是否还没有记录任何事件
+    pub fn is_empty(&self) -> bool {
+        self.replay.events.is_empty()
+    }
+
+    /// 结束录像，记下最终分数和蛇长
+    pub fn finish(mut self, game: &Game) -> Replay {
+        let result = outcome(game);
+        self.replay.score = result.score;
+        self.replay.length = result.length;
+        self.replay
+    }
+}
+
+fn outcome(game: &Game) -> Outcome {
+    Outcome { score: game.score, length: game.snake.len() as u16, state: game.state }
+}
+
+fn direction_to_nibble(dir: Direction) -> u8 {
+    match dir {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
+    }
+}
+
+fn direction_from_nibble(nibble: u8) -> Direction {
+    match nibble {
+        1 => Direction::Up,
+        2 => Direction::Down,
+        3 => Direction::Left,
+        _ => Direction::Right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// 按固定的路线玩一局：绕圈直到撞墙
+    fn record_game(seed: u16, mode: BoardMode, levels: bool) -> Replay {
+        let mut game = Game::with_config(GameConfig::default(), mode, seed);
+        if levels {
+            // 关卡模式同时使用带种类的食物
+            game.set_food_table(&FOOD_TABLE);
+            game.set_levels(&LEVELS);
+        }
+        let mut recorder = Recorder::new(&game, seed);
+
+        // 开始画面的输入不会被记录
+        recorder.set_direction(&mut game, Direction::Up);
+        recorder.update(&mut game);
+        assert!(recorder.is_empty());
+
+        game.press_button();
+        let route = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+        let mut tick = 0;
+        while !game.is_game_over() && tick < 2000 {
+            if tick % 7 == 0 {
+                recorder.set_direction(&mut game, route[(tick / 7) % 4]);
+            }
+            // 偶尔在同一 tick 内连续转向
+            if tick % 13 == 0 {
+                recorder.set_direction(&mut game, route[(tick / 13 + 1) % 4]);
+            }
+            recorder.update(&mut game);
+            tick += 1;
+        }
+        for _ in 0..30 {
+            recorder.update(&mut game);
+        }
+        recorder.set_direction(&mut game, Direction::Left);
+        recorder.update(&mut game);
+        recorder.finish(&game)
+    }
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let replay = Replay {
+            seed: 0xBEEF,
+            cols: 32,
+            rows: 24,
+            mode: BoardMode::Wrap,
+            levels: true,
+            two_player: false,
+            typed_food: true,
+            events: vec![
+                Event::Tick,
+                Event::Turn(Direction::Up),
+                Event::Turn(Direction::Left),
+                Event::Tick,
+                Event::Tick,
+                Event::Turn(Direction::Down),
+            ],
+            score: 12,
+            length: 15,
+        };
+        let bytes = replay.encode();
+        assert_eq!(&bytes[..4], &[b'S', b'R', VERSION, FLAG_WRAP | FLAG_LEVELS | FLAG_FOOD_TABLE]);
+        assert_eq!(bytes.len(), HEADER_LEN + 3);
+        assert_eq!(Replay::decode(&bytes), Ok(replay));
+    }
+
+    #[test]
+    fn long_idle_runs_are_compact() {
+        let replay = Replay {
+            seed: 1,
+            cols: 24,
+            rows: 24,
+            mode: BoardMode::Walls,
+            levels: false,
+            two_player: false,
+            typed_food: false,
+            events: vec![Event::Tick; 100],
+            score: 0,
+            length: 3,
+        };
+        let bytes = replay.encode();
+        // 100 = 9 * 11 + 1，共 10 个半字节
+        assert_eq!(bytes.len(), HEADER_LEN + 5);
+        assert_eq!(Replay::decode(&bytes).unwrap().events.len(), 100);
+    }
+
+    #[test]
+    fn player2_turns_roundtrip() {
+        let replay = Replay {
+            seed: 7,
+            cols: 24,
+            rows: 24,
+            mode: BoardMode::Walls,
+            levels: false,
+            two_player: true,
+            typed_food: false,
+            events: vec![
+                Event::Turn2(Direction::Up),
+                Event::Tick,
+                Event::Turn(Direction::Down),
+                Event::Turn2(Direction::Right),
+            ],
+            score: 0,
+            length: 3,
+        };
+        let bytes = replay.encode();
+        assert_eq!(bytes[3], FLAG_TWO_PLAYER);
+        // 0 1 | 5 2 | 0 4
+        assert_eq!(&bytes[HEADER_LEN..], &[0x01, 0x52, 0x04]);
+        assert_eq!(Replay::decode(&bytes), Ok(replay));
+    }
+
+    #[test]
+    fn two_player_game_replays_exactly() {
+        let mut game = Game::with_seed(11);
+        game.set_two_player(true);
+        game.set_levels(&LEVELS);
+        let mut recorder = Recorder::new(&game, 11);
+        game.press_button();
+
+        let turns = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+        let mut tick = 0;
+        while !game.is_game_over() && tick < 500 {
+            if tick % 5 == 0 {
+                recorder.set_direction(&mut game, turns[(tick / 5) % 4]);
+            }
+            if tick % 6 == 0 {
+                recorder.set_player2_direction(&mut game, turns[(tick / 6 + 2) % 4]);
+            }
+            recorder.update(&mut game);
+            tick += 1;
+        }
+        let result = game.result;
+        let replay = Replay::decode(&recorder.finish(&game).encode()).unwrap();
+        assert!(replay.two_player);
+        replay.verify().unwrap();
+        let mut replayed = replay.new_game();
+        for event in &replay.events {
+            match event {
+                Event::Turn(dir) => replayed.set_direction(*dir),
+                Event::Turn2(dir) => replayed.set_player2_direction(*dir),
+                Event::Tick => replayed.update(),
+            }
+        }
+        assert_eq!(replayed.result, result);
+    }
+
+    /// 与 main.rs 完全相同的创建顺序，录一局再重放
+    #[test]
+    fn device_setup_replays_exactly() {
+        use crate::snake::Difficulty;
+
+        for two_player in [false, true] {
+            let seed = 4321;
+            let mut game = Game::with_config(GameConfig::new(24, 24, 10), BoardMode::Walls, seed);
+            game.difficulty = Difficulty::Normal;
+            game.set_food_table(&FOOD_TABLE);
+            game.set_two_player(two_player);
+            game.set_levels(&LEVELS);
+            let mut recorder = Recorder::new(&game, seed);
+
+            game.press_button();
+            let replay = Replay::decode(&recorder.replay.encode()).unwrap();
+            let mut replayed = replay.new_game();
+            assert_eq!(replayed.food, game.food, "two_player {two_player}");
+
+            let turns = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+            let mut tick = 0;
+            while !game.is_finished() && tick < 500 {
+                if tick % 5 == 0 {
+                    recorder.set_direction(&mut game, turns[(tick / 5) % 4]);
+                }
+                if two_player && tick % 6 == 0 {
+                    recorder.set_player2_direction(&mut game, turns[(tick / 6 + 2) % 4]);
+                }
+                recorder.update(&mut game);
+                tick += 1;
+            }
+
+            let replay = Replay::decode(&recorder.finish(&game).encode()).unwrap();
+            replay.verify().unwrap();
+            replayed = replay.new_game();
+            for event in &replay.events {
+                match event {
+                    Event::Turn(dir) => replayed.set_direction(*dir),
+                    Event::Turn2(dir) => replayed.set_player2_direction(*dir),
+                    Event::Tick => replayed.update(),
+                }
+            }
+            assert_eq!(replayed.food, game.food);
+            assert!(replayed.snake.iter().eq(game.snake.iter()));
+            assert_eq!(replayed.state, game.state);
+        }
+    }
+
+    #[test]
+    fn decode_rejects_bad_input() {
+        assert_eq!(Replay::decode(b"SR"), Err(DecodeError::Truncated));
+        assert_eq!(Replay::decode(&[0; HEADER_LEN]), Err(DecodeError::BadMagic));
+        let mut bytes = [0; HEADER_LEN];
+        bytes[..3].copy_from_slice(&[b'S', b'R', 9]);
+        assert_eq!(Replay::decode(&bytes), Err(DecodeError::UnsupportedVersion(9)));
+    }
+
+    #[test]
+    fn recorded_game_replays_exactly() {
+        for (seed, mode, levels) in [
+            (1, BoardMode::Walls, false),
+            (4242, BoardMode::Walls, true),
+            (999, BoardMode::Wrap, false),
+        ] {
+            let replay = record_game(seed, mode, levels);
+            let decoded = Replay::decode(&replay.encode()).unwrap();
+            let outcome = decoded.verify().unwrap();
+            assert_eq!(outcome.score, replay.score);
+            assert_eq!(outcome.length, replay.length);
+        }
+    }
+
+    #[test]
+    fn tampered_replay_fails_verification() {
+        let mut replay = record_game(1, BoardMode::Walls, false);
+        replay.score += 1;
+        let err = replay.verify().unwrap_err();
+        assert_eq!(err.actual.score + 1, err.expected_score);
+        assert_eq!(err.actual.length, err.expected_length);
+    }
+
+    #[test]
+    fn recorder_header_follows_game() {
+        let mut game = Game::with_seed(3);
+        let replay = Recorder::new(&game, 3).finish(&game);
+        assert!(!replay.levels && !replay.two_player && !replay.typed_food);
+
+        game.set_levels(&LEVELS);
+        game.set_two_player(true);
+        let replay = Recorder::new(&game, 3).finish(&game);
+        assert!(replay.levels && replay.two_player);
+    }
+
+    #[test]
+    fn replay_after_reset_with_seed() {
+        let mut game = Game::with_seed(5);
+        game.set_levels(&LEVELS);
+        game.reset_with_seed(321);
+        let mut recorder = Recorder::new(&game, 321);
+        game.press_button();
+        for _ in 0..40 {
+            recorder.update(&mut game);
+        }
+        let replay = recorder.finish(&game);
+        assert_eq!(replay.run().length, game.snake.len() as u16);
+        assert_eq!(replay.run().state, game.state);
+    }
+}
@@ -1,37 +1,87 @@
 //! 贪吃蛇游戏逻辑模块
 
-use alloc::vec::Vec;
+use alloc::collections::VecDeque;
 use alloc::boxed::Box;
+#[cfg(target_os = "none")]
 use defmt::Format;
 
-// 随机数种子（由 main.rs 设置）
-static mut RANDOM_SEED: u16 = 0;
+use crate::body::Body;
+use crate::food::{self, FoodKind, MIN_LEN, NORMAL_ONLY, SHRINK_LEN, SPEED_UP_PERCENT, SPEED_UP_TICKS};
+use crate::level::{LEVEL_UP_SCORE, Level};
 
-/// 设置随机数种子
-pub fn set_random_seed(seed: u16) {
-    unsafe {
-        RANDOM_SEED = seed;
+/// 随机数源
+///
+/// 每个 `Game` 拥有自己的随机数源，互不干扰，测试中可注入固定序列
+pub trait Rng {
+    /// 获取下一个伪随机数
+    fn next_u16(&mut self) -> u16;
+}
+
+/// 简单的线性同余生成器（默认随机数源）
+#[derive(Clone, Copy, Debug)]
+pub struct Lcg {
+    seed: u16,
+}
+
+impl Lcg {
+    /// 使用指定种子创建
+    pub const fn new(seed: u16) -> Self {
+        Self { seed }
     }
 }
 
-/// 获取伪随机数
-fn random() -> u16 {
-    unsafe {
-        // 简单的线性同余生成器
-        let seed = RANDOM_SEED;
-        let new_seed = seed.wrapping_mul(25173).wrapping_add(13849);
-        RANDOM_SEED = new_seed;
-        new_seed
+impl Rng for Lcg {
+    fn next_u16(&mut self) -> u16 {
+        self.seed = self.seed.wrapping_mul(25173).wrapping_add(13849);
+        self.seed
     }
 }
 
-// 游戏常量
-pub const GRID_SIZE: u16 = 10;          // 每格大小 10x10 像素
-pub const GRID_COUNT: u16 = 24;         // 24x24 格子
-pub const SCREEN_SIZE: u16 = 240;       // 屏幕大小 240x240
+/// 棋盘尺寸配置
+///
+/// 游戏逻辑使用 `cols`/`rows`，渲染使用 `cell_px`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GameConfig {
+    /// 列数
+    pub cols: u16,
+    /// 行数
+    pub rows: u16,
+    /// 每格像素大小
+    pub cell_px: u16,
+}
+
+impl Default for GameConfig {
+    /// 240x240 屏幕，24x24 格子，每格 10x10 像素
+    fn default() -> Self {
+        Self::new(24, 24, 10)
+    }
+}
+
+impl GameConfig {
+    /// 创建新配置
+    pub const fn new(cols: u16, rows: u16, cell_px: u16) -> Self {
+        Self { cols, rows, cell_px }
+    }
+
+    /// 按屏幕像素尺寸创建配置，多余的像素不使用
+    pub const fn for_panel(width: u16, height: u16, cell_px: u16) -> Self {
+        Self::new(width / cell_px, height / cell_px, cell_px)
+    }
+
+    /// 棋盘像素宽度
+    pub const fn width_px(&self) -> u32 {
+        self.cols as u32 * self.cell_px as u32
+    }
+
+    /// 棋盘像素高度
+    pub const fn height_px(&self) -> u32 {
+        self.rows as u32 * self.cell_px as u32
+    }
+}
 
 /// 方向枚举
-#[derive(Clone, Copy, PartialEq, Format)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(target_os = "none", derive(Format))]
 pub enum Direction {
     Up,
     Down,
@@ -51,123 +101,1556 @@ impl Direction {
     }
 }
 
+/// 每个 tick 之间最多缓存的转向次数
+pub const INPUT_QUEUE_LEN: usize = 3;
+
+/// 边界模式
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[cfg_attr(target_os = "none", derive(Format))]
+pub enum BoardMode {
+    /// 撞到边界游戏结束
+    #[default]
+    Walls,
+    /// 从一侧穿出，从对侧进入
+    Wrap,
+}
+
+/// 难度，决定游戏速度曲线
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[cfg_attr(target_os = "none", derive(Format))]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// 初始 tick 间隔（毫秒）
+    pub const fn base_ms(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 200,
+            Difficulty::Normal => 150,
+            Difficulty::Hard => 100,
+        }
+    }
+
+    /// 每得 1 分缩短的毫秒数
+    pub const fn step_ms(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 2,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 4,
+        }
+    }
+
+    /// 最短 tick 间隔（毫秒）
+    pub const fn min_ms(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 100,
+            Difficulty::Normal => 70,
+            Difficulty::Hard => 50,
+        }
+    }
+
+    /// 根据分数计算 tick 间隔：随分数线性加速，不低于最短间隔
+    pub fn tick_interval_ms(&self, score: u16) -> u32 {
+        let speedup = self.step_ms().saturating_mul(score as u32);
+        self.base_ms().saturating_sub(speedup).max(self.min_ms())
+    }
+}
+
+/// 游戏状态机
+///
+/// ```text
+/// Title --按键--> Playing --按键--> Paused --按键--> Playing
+///                    |
+///                  撞击
+///                    v
+///                 GameOver --按键--> Title（重置）
+/// ```
+///
+/// 吃完最后一个食物后没有空格子可以放新食物时进入 `Cleared`（获胜），
+/// 按键同样回到开始画面
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(target_os = "none", derive(Format))]
+pub enum GameState {
+    /// 开始画面
+    Title,
+    /// 游戏进行中
+    Playing,
+    /// 暂停
+    Paused,
+    /// 游戏结束，显示最终得分
+    GameOver,
+    /// 蛇占满了棋盘，获胜
+    Cleared,
+}
+
 /// 位置坐标
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Position {
     pub x: u16,
     pub y: u16,
 }
 
+/// 双人模式的结果
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(target_os = "none", derive(Format))]
+pub enum MatchResult {
+    Player1Wins,
+    Player2Wins,
+    /// 两条蛇同时撞上（包括头对头相撞）
+    Draw,
+}
+
+/// 第二名玩家（双人模式）
+///
+/// 第一名玩家使用 `Game` 自身的 `snake`、`direction`、`score`
+pub struct Player {
+    pub snake: Body,
+    pub direction: Direction,
+    /// 待执行的转向，每次 `update` 取出一个
+    pending: VecDeque<Direction>,
+    pub score: u16,
+}
+
 /// 游戏状态
 pub struct Game {
-    pub snake: Vec<Position>,
+    pub snake: Body,
     pub direction: Direction,
-    next_direction: Direction,
+    /// 待执行的转向，每次 `update` 取出一个
+    pending: VecDeque<Direction>,
     pub food: Position,
+    pub food_kind: FoodKind,
+    /// 食物剩余的 tick 数，None 为一直存在
+    pub food_ticks: Option<u16>,
+    /// 加速剩余的 tick 数
+    pub boost_ticks: u16,
     pub score: u16,
-    pub game_over: bool,
+    pub state: GameState,
+    pub mode: BoardMode,
+    pub config: GameConfig,
+    pub difficulty: Difficulty,
+    /// 当前关卡的障碍物
+    pub walls: Level,
+    /// 当前关卡序号（从 0 开始）
+    pub level: usize,
+    /// 第二条蛇，单人模式为 None
+    pub player2: Option<Player>,
+    /// 双人模式的结果，游戏结束时设置
+    pub result: Option<MatchResult>,
+    levels: &'static [&'static str],
+    food_table: &'static [(FoodKind, u16)],
+    rng: Box<dyn Rng>,
 }
 
 impl Game {
-    /// 创建新游戏
+    /// 创建新游戏（种子为 0）
     pub fn new() -> Box<Self> {
-        // 初始化蛇在屏幕中央，长度为3
-        let start_x = GRID_COUNT / 2;
-        let start_y = GRID_COUNT / 2;
-        let snake = alloc::vec![
-            Position { x: start_x, y: start_y },
-            Position { x: start_x - 1, y: start_y },
-            Position { x: start_x - 2, y: start_y },
-        ];
-        
+        Self::with_seed(0)
+    }
+
+    /// 使用指定种子创建新游戏，相同种子得到相同的食物序列
+    pub fn with_seed(seed: u16) -> Box<Self> {
+        Self::with_mode(BoardMode::Walls, seed)
+    }
+
+    /// 使用指定边界模式和种子创建新游戏
+    pub fn with_mode(mode: BoardMode, seed: u16) -> Box<Self> {
+        Self::with_config(GameConfig::default(), mode, seed)
+    }
+
+    /// 使用指定棋盘尺寸、边界模式和种子创建新游戏
+    pub fn with_config(config: GameConfig, mode: BoardMode, seed: u16) -> Box<Self> {
+        Self::with_rng(config, mode, Box::new(Lcg::new(seed)))
+    }
+
+    /// 使用自定义随机数源创建新游戏
+    ///
+    /// 棋盘至少需要 4 列才能放下初始的蛇
+    pub fn with_rng(config: GameConfig, mode: BoardMode, rng: Box<dyn Rng>) -> Box<Self> {
+        assert!(config.cols >= 4 && config.rows >= 1, "棋盘太小");
+
         let mut game = Box::new(Game {
-            snake,
+            snake: Body::new(&config),
             direction: Direction::Right,
-            next_direction: Direction::Right,
+            pending: VecDeque::with_capacity(INPUT_QUEUE_LEN),
             food: Position { x: 0, y: 0 },
+            food_kind: FoodKind::Normal,
+            food_ticks: None,
+            boost_ticks: 0,
             score: 0,
-            game_over: false,
+            state: GameState::Title,
+            mode,
+            config,
+            difficulty: Difficulty::default(),
+            walls: Level::empty(&config),
+            level: 0,
+            player2: None,
+            result: None,
+            levels: &[],
+            food_table: &NORMAL_ONLY,
+            rng,
         });
+        game.place_snake();
         game.spawn_food();
         game
     }
 
-    /// 生成食物（不在蛇身上）
-    fn spawn_food(&mut self) {
-        // 使用伪随机数生成位置
-        loop {
-            let new_food = Position {
-                x: random() % GRID_COUNT,
-                y: random() % GRID_COUNT,
-            };
-            
-            // 检查是否在蛇身上
-            let on_snake = self.snake.iter().any(|p| p.x == new_food.x && p.y == new_food.y);
-            if !on_snake {
-                self.food = new_food;
-                break;
+    /// 是否游戏结束
+    pub fn is_game_over(&self) -> bool {
+        self.state == GameState::GameOver
+    }
+
+    /// 这一局是否已经结束（撞上或占满棋盘）
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, GameState::GameOver | GameState::Cleared)
+    }
+
+    /// 按键（摇杆 Z 按钮）驱动状态切换
+    pub fn press_button(&mut self) {
+        match self.state {
+            GameState::Title => self.state = GameState::Playing,
+            GameState::Playing => self.state = GameState::Paused,
+            GameState::Paused => self.state = GameState::Playing,
+            GameState::GameOver | GameState::Cleared => self.reset(),
+        }
+    }
+
+    /// 当前分数和难度下，两次 `update` 之间应等待的毫秒数（加速期间更短）
+    pub fn tick_interval_ms(&self) -> u32 {
+        let interval = self.difficulty.tick_interval_ms(self.score);
+        if self.boost_ticks > 0 {
+            interval * SPEED_UP_PERCENT / 100
+        } else {
+            interval
+        }
+    }
+
+    /// 设置食物概率表（见 `food::FOOD_TABLE`），从下一次生成食物开始生效
+    pub fn set_food_table(&mut self, table: &'static [(FoodKind, u16)]) {
+        self.food_table = table;
+    }
+
+    /// 当前的食物概率表
+    pub fn food_table(&self) -> &'static [(FoodKind, u16)] {
+        self.food_table
+    }
+
+    /// 设置关卡列表（ASCII 地图）并从第一关开始
+    ///
+    /// 每关得到 `LEVEL_UP_SCORE` 分后进入下一关，最后一关不再前进
+    pub fn set_levels(&mut self, levels: &'static [&'static str]) {
+        self.levels = levels;
+        self.load_level(0);
+    }
+
+    /// 开启或关闭双人模式，两条蛇回到起点
+    ///
+    /// 双人模式下棋盘至少需要 3 行。模式没有变化时什么都不做（不消耗随机数），
+    /// 所以 `set_two_player(false)` 不会让单人游戏与录像不一致
+    pub fn set_two_player(&mut self, enabled: bool) {
+        assert!(!enabled || self.config.rows >= 3, "棋盘太小");
+        if enabled == self.player2.is_some() {
+            return;
+        }
+        self.player2 = enabled.then(|| Player {
+            snake: Body::new(&self.config),
+            direction: Direction::Left,
+            pending: VecDeque::with_capacity(INPUT_QUEUE_LEN),
+            score: 0,
+        });
+        self.place_snake();
+        self.spawn_food();
+    }
+
+    /// 是否为双人模式
+    pub fn is_two_player(&self) -> bool {
+        self.player2.is_some()
+    }
+
+    /// 是否设置了关卡列表（见 `set_levels`）
+    pub fn has_levels(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    /// 加载关卡：替换障碍物，蛇回到起点，分数保留
+    fn load_level(&mut self, index: usize) {
+        self.level = index;
+        self.walls = match self.levels.get(index) {
+            Some(map) => Level::parse(map, &self.config),
+            None => Level::empty(&self.config),
+        };
+        self.place_snake();
+        self.spawn_food();
+    }
+
+    /// 初始化蛇在屏幕中央，长度为3，向右移动
+    ///
+    /// 双人模式下第一条蛇在上方三分之一处向右，第二条蛇在下方三分之一处向左
+    fn place_snake(&mut self) {
+        let start_x = self.config.cols / 2;
+        let start_y = match self.player2 {
+            Some(_) => self.config.rows / 3,
+            None => self.config.rows / 2,
+        };
+        self.snake = Body::from_cells(&self.config, [
+            Position { x: start_x, y: start_y },
+            Position { x: start_x - 1, y: start_y },
+            Position { x: start_x - 2, y: start_y },
+        ]);
+        self.direction = Direction::Right;
+        self.pending.clear();
+
+        if let Some(p2) = &mut self.player2 {
+            let x = start_x - 1;
+            let y = self.config.rows - 1 - self.config.rows / 3;
+            p2.snake = Body::from_cells(&self.config, [
+                Position { x, y },
+                Position { x: x + 1, y },
+                Position { x: x + 2, y },
+            ]);
+            p2.direction = Direction::Left;
+            p2.pending.clear();
+        }
+
+        // 起点上的墙清除掉，避免一开始就撞墙
+        let p2_snake = self.player2.iter().flat_map(|p| &p.snake);
+        for pos in self.snake.iter().chain(p2_snake) {
+            self.walls.set_wall(*pos, false);
+        }
+    }
+
+    /// 所有玩家的分数之和
+    fn total_score(&self) -> u16 {
+        self.score + self.player2.as_ref().map_or(0, |p| p.score)
+    }
+
+    /// 格子是否被任意一条蛇占据（O(1)，查询蛇身的占用位图）
+    pub fn is_occupied(&self, pos: Position) -> bool {
+        self.snake.contains(&pos) || self.player2.as_ref().is_some_and(|p| p.snake.contains(&pos))
+    }
+
+    /// 是否达到进入下一关的分数
+    fn should_level_up(&self) -> bool {
+        self.level + 1 < self.levels.len()
+            && self.total_score() >= (self.level as u16 + 1) * LEVEL_UP_SCORE
+    }
+
+    /// 按行遍历所有空格子（不在蛇身上，也不在墙上）
+    fn free_cells(&self) -> impl Iterator<Item = Position> + '_ {
+        let cols = self.config.cols;
+        (0..self.config.rows)
+            .flat_map(move |y| (0..cols).map(move |x| Position { x, y }))
+            .filter(|pos| !self.is_occupied(*pos) && !self.walls.is_wall(*pos))
+    }
+
+    /// 生成食物：在所有空格子（不在蛇身上，也不在墙上）中均匀地随机选一个
+    ///
+    /// 只消耗一个随机数，每格的检查是 O(1)，时间与棋盘大小成正比。
+    /// 没有空格子时返回 false，食物不变
+    fn spawn_food(&mut self) -> bool {
+        let count = self.free_cells().count();
+        if count == 0 {
+            return false;
+        }
+
+        // 用随机数的高位把 0..65536 映射到 0..count（LCG 的低位周期很短）
+        let n = (self.rng.next_u16() as usize * count) >> 16;
+        let food = self.free_cells().nth(n).unwrap();
+        self.food = food;
+
+        // 概率表只有一项时不消耗随机数，与只有普通食物时的食物序列相同
+        self.food_kind = match self.food_table {
+            [(kind, _)] => *kind,
+            table => food::pick(table, self.rng.next_u16()),
+        };
+        self.food_ticks = self.food_kind.lifetime();
+        true
+    }
+
+    /// 吃到食物之后：加速食物开始加速，然后进入下一关或生成新食物，
+    /// 棋盘已满时获胜
+    fn after_eating(&mut self, kind: FoodKind) {
+        if kind == FoodKind::SpeedUp {
+            self.boost_ticks = SPEED_UP_TICKS;
+        }
+        if self.should_level_up() {
+            self.load_level(self.level + 1);
+        } else if !self.spawn_food() {
+            // 双人模式下另一条蛇的蛇尾总会让出一格，不会出现这种情况
+            self.state = GameState::Cleared;
+        }
+    }
+
+    /// 食物没被吃到：倒计时，超时后在别处重新生成
+    fn age_food(&mut self) {
+        if let Some(ticks) = &mut self.food_ticks {
+            *ticks = ticks.saturating_sub(1);
+            if *ticks == 0 {
+                self.spawn_food();
             }
         }
     }
 
-    /// 设置方向（防止180度转向）
+    /// 设置方向（加入转向队列，防止180度转向）
+    ///
+    /// 新方向与队列中最后一个方向（队列为空时为当前方向）比较，
+    /// 相同或相反的方向被忽略，队列满时丢弃
     pub fn set_direction(&mut self, dir: Direction) {
-        // defmt::println!("set_direction: 当前方向={}, 请求方向={}", self.direction, dir);
-        // 检查是否与当前移动方向相反
-        if dir != self.direction.opposite() {
-            self.next_direction = dir;
-            self.direction = dir;
-            defmt::println!("方向已更新为: {}", self.direction);
-        } else {
-            defmt::println!("方向被拒绝（180度转向）");
+        // 只在游戏进行中接受转向，暂停时的输入不会累积
+        if self.state != GameState::Playing {
+            return;
+        }
+        queue_turn(&mut self.pending, self.direction, dir);
+    }
+
+    /// 设置第二条蛇的方向（规则与 `set_direction` 相同），单人模式下忽略
+    pub fn set_player2_direction(&mut self, dir: Direction) {
+        if self.state != GameState::Playing {
+            return;
+        }
+        if let Some(p2) = &mut self.player2 {
+            queue_turn(&mut p2.pending, p2.direction, dir);
         }
     }
 
-    /// 游戏更新
+    /// 游戏更新（只在 `Playing` 状态下移动）
     pub fn update(&mut self) {
-        if self.game_over {
+        if self.state != GameState::Playing {
             return;
         }
 
-        // 计算新头部位置
-        let head = self.snake.first().unwrap();
-        // defmt::println!("更新: 方向={}, 蛇头位置=({},{})", self.direction, head.x, head.y);
-        
-        let new_head = match self.direction {
-            Direction::Up => Position { x: head.x, y: head.y.wrapping_sub(1) },
-            Direction::Down => Position { x: head.x, y: head.y.saturating_add(1) },
-            Direction::Left => Position { x: head.x.wrapping_sub(1), y: head.y },
-            Direction::Right => Position { x: head.x.saturating_add(1), y: head.y },
+        if self.player2.is_some() {
+            self.update_two_player();
+            return;
+        }
+
+        self.direction = next_direction(&mut self.pending, self.direction);
+
+        // 计算新头部位置，撞墙时为 None
+        let head = self.snake.head().unwrap();
+        let Some(new_head) = self.step(head, self.direction) else {
+            self.state = GameState::GameOver;
+            return;
         };
-        
 
-        // 检查撞墙
-        if new_head.x >= GRID_COUNT || new_head.y >= GRID_COUNT {
-            self.game_over = true;
+        // 检查撞障碍物
+        if self.walls.is_wall(new_head) {
+            self.state = GameState::GameOver;
             return;
         }
 
         // 检查撞自己
-        if self.snake.iter().any(|p| p.x == new_head.x && p.y == new_head.y) {
-            self.game_over = true;
+        if self.snake.contains(&new_head) {
+            self.state = GameState::GameOver;
             return;
         }
 
         // 移动蛇
-        self.snake.insert(0, new_head);
+        self.snake.push_front(new_head);
+        self.boost_ticks = self.boost_ticks.saturating_sub(1);
 
         // 检查吃食物
         if new_head.x == self.food.x && new_head.y == self.food.y {
-            self.score += 1;
-            self.spawn_food();
+            let kind = self.food_kind;
+            self.score += kind.points();
+            feed(&mut self.snake, kind);
+            self.after_eating(kind);
+        } else {
+            self.snake.pop_back();
+            self.age_food();
+        }
+    }
+
+    /// 双人模式的更新：两条蛇同时移动
+    ///
+    /// 撞到边界、障碍物或任意一条蛇（移动前的位置，包括蛇尾）的蛇输掉，
+    /// 两个蛇头进入同一格时双方都输，两条蛇同时撞上为平局
+    fn update_two_player(&mut self) {
+        let Some(mut p2) = self.player2.take() else {
+            return;
+        };
+
+        self.direction = next_direction(&mut self.pending, self.direction);
+        p2.direction = next_direction(&mut p2.pending, p2.direction);
+
+        let head1 = self.step(self.snake[0], self.direction);
+        let head2 = self.step(p2.snake[0], p2.direction);
+        let crashed = |head: Option<Position>| match head {
+            Some(pos) => self.walls.is_wall(pos) || self.snake.contains(&pos) || p2.snake.contains(&pos),
+            None => true,
+        };
+        let head_on = head1.is_some() && head1 == head2;
+        let dead1 = head_on || crashed(head1);
+        let dead2 = head_on || crashed(head2);
+
+        let (Some(head1), Some(head2), false, false) = (head1, head2, dead1, dead2) else {
+            self.result = Some(match (dead1, dead2) {
+                (true, true) => MatchResult::Draw,
+                (true, false) => MatchResult::Player2Wins,
+                _ => MatchResult::Player1Wins,
+            });
+            self.state = GameState::GameOver;
+            self.player2 = Some(p2);
+            return;
+        };
+
+        self.snake.push_front(head1);
+        p2.snake.push_front(head2);
+        self.boost_ticks = self.boost_ticks.saturating_sub(1);
+
+        // 两个蛇头不在同一格，最多一条蛇吃到食物
+        let kind = self.food_kind;
+        let ate1 = head1 == self.food;
+        let ate2 = head2 == self.food;
+        if ate1 {
+            self.score += kind.points();
+            feed(&mut self.snake, kind);
+        } else {
+            self.snake.pop_back();
+        }
+        if ate2 {
+            p2.score += kind.points();
+            feed(&mut p2.snake, kind);
+        } else {
+            p2.snake.pop_back();
+        }
+        self.player2 = Some(p2);
+
+        if ate1 || ate2 {
+            self.after_eating(kind);
         } else {
-            self.snake.pop();
+            self.age_food();
         }
     }
 
-    /// 重置游戏
+    /// 沿指定方向移动一格，按边界模式处理越界
+    pub(crate) fn step(&self, pos: Position, dir: Direction) -> Option<Position> {
+        let last_x = self.config.cols - 1;
+        let last_y = self.config.rows - 1;
+        let (x, y) = match (self.mode, dir) {
+            (BoardMode::Walls, Direction::Up) => (pos.x, pos.y.checked_sub(1)?),
+            (BoardMode::Walls, Direction::Down) if pos.y < last_y => (pos.x, pos.y + 1),
+            (BoardMode::Walls, Direction::Left) => (pos.x.checked_sub(1)?, pos.y),
+            (BoardMode::Walls, Direction::Right) if pos.x < last_x => (pos.x + 1, pos.y),
+            (BoardMode::Walls, _) => return None,
+            (BoardMode::Wrap, Direction::Up) => (pos.x, if pos.y == 0 { last_y } else { pos.y - 1 }),
+            (BoardMode::Wrap, Direction::Down) => (pos.x, if pos.y == last_y { 0 } else { pos.y + 1 }),
+            (BoardMode::Wrap, Direction::Left) => (if pos.x == 0 { last_x } else { pos.x - 1 }, pos.y),
+            (BoardMode::Wrap, Direction::Right) => (if pos.x == last_x { 0 } else { pos.x + 1 }, pos.y),
+        };
+        Some(Position { x, y })
+    }
+
+    /// 相邻两格之间的方向（穿墙模式下跨越边界也算相邻），不相邻时返回 None
+    pub(crate) fn direction_between(&self, from: Position, to: Position) -> Option<Direction> {
+        [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+            .into_iter()
+            .find(|dir| self.step(from, *dir) == Some(to))
+    }
+
+    /// 重置游戏，回到开始画面（沿用当前随机数源、棋盘尺寸、边界模式、难度、
+    /// 食物概率表、玩家人数和关卡列表，回到第一关）
     pub fn reset(&mut self) {
-        let new_game = Self::new();
+        let rng = core::mem::replace(&mut self.rng, Box::new(Lcg::new(0)));
+        let mut new_game = Self::with_rng(self.config, self.mode, rng);
+        new_game.difficulty = self.difficulty;
+        new_game.food_table = self.food_table;
+        if self.is_two_player() {
+            new_game.set_two_player(true);
+        }
+        if !self.levels.is_empty() {
+            new_game.set_levels(self.levels);
+        }
         *self = *new_game;
     }
+
+    /// 使用新种子重置游戏，回到开始画面（录像需要每局从已知种子开始）
+    pub fn reset_with_seed(&mut self, seed: u16) {
+        self.rng = Box::new(Lcg::new(seed));
+        self.reset();
+    }
+}
+
+/// 蛇头已经移到食物上（蛇身多了一节）：缩短食物再去掉几节
+fn feed(snake: &mut Body, kind: FoodKind) {
+    if kind == FoodKind::Shrink {
+        let len = (snake.len() - 1).saturating_sub(SHRINK_LEN).max(MIN_LEN);
+        snake.truncate(len);
+    }
+}
+
+/// 把转向加入队列
+///
+/// 新方向与队列中最后一个方向（队列为空时为当前方向）比较，
+/// 相同或相反的方向被忽略，队列满时丢弃
+fn queue_turn(pending: &mut VecDeque<Direction>, current: Direction, dir: Direction) {
+    let last = pending.back().copied().unwrap_or(current);
+    if dir == last {
+        return;
+    }
+
+    if dir == last.opposite() {
+        #[cfg(target_os = "none")]
+        defmt::println!("方向被拒绝（180度转向）");
+        return;
+    }
+
+    if pending.len() >= INPUT_QUEUE_LEN {
+        #[cfg(target_os = "none")]
+        defmt::println!("方向被丢弃（队列已满）");
+        return;
+    }
+
+    pending.push_back(dir);
+    #[cfg(target_os = "none")]
+    defmt::println!("方向已加入队列: {}", dir);
+}
+
+/// 本次 tick 实际生效的方向（最多执行一次转向）
+fn next_direction(pending: &mut VecDeque<Direction>, current: Direction) -> Direction {
+    while let Some(dir) = pending.pop_front() {
+        if dir != current.opposite() {
+            return dir;
+        }
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    const COLS: u16 = 24;
+    const ROWS: u16 = 24;
+
+    /// 固定序列随机数源
+    struct SeqRng {
+        values: Vec<u16>,
+        index: usize,
+    }
+
+    impl Rng for SeqRng {
+        fn next_u16(&mut self) -> u16 {
+            let value = self.values[self.index % self.values.len()];
+            self.index += 1;
+            value
+        }
+    }
+
+    /// 跳过开始画面，直接进入游戏
+    fn started(mut game: Box<Game>) -> Box<Game> {
+        game.press_button();
+        game
+    }
+
+    fn pos(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    /// 默认棋盘上的蛇身（蛇头在前）
+    fn body(cells: &[Position]) -> Body {
+        Body::from_cells(&GameConfig::default(), cells.iter().copied())
+    }
+
+    #[test]
+    fn lcg_matches_previous_generator() {
+        let mut rng = Lcg::new(0);
+        assert_eq!(rng.next_u16(), 13849);
+        assert_eq!(rng.next_u16(), 13849u16.wrapping_mul(25173).wrapping_add(13849));
+    }
+
+    #[test]
+    fn same_seed_same_game() {
+        let mut a = started(Game::with_seed(42));
+        let mut b = started(Game::with_seed(42));
+        assert_eq!(a.food, b.food);
+        for _ in 0..5 {
+            a.update();
+            b.update();
+        }
+        assert_eq!(a.snake, b.snake);
+        assert_eq!(a.food, b.food);
+    }
+
+    #[test]
+    fn games_do_not_share_rng() {
+        let a = Game::with_seed(7);
+        let _other = Game::with_seed(1234);
+        let b = Game::with_seed(7);
+        assert_eq!(a.food, b.food);
+    }
+
+    /// 初始棋盘（24x24，蛇占 3 格）上的空格子数
+    const FREE: usize = (COLS * ROWS) as usize - 3;
+
+    /// 让 `spawn_food` 选中第 `n` 个空格子（共 `free` 个）的随机数
+    fn roll(n: usize, free: usize) -> u16 {
+        (n * 65536).div_ceil(free) as u16
+    }
+
+    /// 初始棋盘上第 `n` 个空格子（跳过 (10..=12, 12) 上的蛇）
+    fn nth_free(n: usize) -> Position {
+        let snake_start = (12 * COLS + 10) as usize;
+        let i = if n < snake_start { n } else { n + 3 };
+        pos(i as u16 % COLS, i as u16 / COLS)
+    }
+
+    #[test]
+    fn injected_rng_places_food() {
+        let game = Game::with_rng(GameConfig::default(), BoardMode::Walls, Box::new(SeqRng { values: vec![roll(123, FREE)], index: 0 }));
+        assert_eq!(game.food, pos(3, 5));
+    }
+
+    #[test]
+    fn food_skips_snake_body() {
+        // 第 298 个格子是蛇尾 (10,12)，空格子从蛇头后面 (13,12) 接着数
+        let game = Game::with_rng(GameConfig::default(), BoardMode::Walls, Box::new(SeqRng { values: vec![roll(298, FREE)], index: 0 }));
+        assert_eq!(game.food, pos(13, 12));
+        assert_eq!(nth_free(298), pos(13, 12));
+    }
+
+    #[test]
+    fn spawn_is_uniform_over_free_cells() {
+        // 4x2 棋盘，蛇占 3 格，剩 5 个空格子
+        let values = (0..=255u16).map(|v| v << 8).collect();
+        let mut game = Game::with_rng(GameConfig::new(4, 2, 10), BoardMode::Walls, Box::new(SeqRng { values, index: 0 }));
+        let mut counts = [0; 8];
+        for _ in 0..255 {
+            assert!(game.spawn_food());
+            assert!(!game.snake.contains(&game.food));
+            counts[(game.food.y * 4 + game.food.x) as usize] += 1;
+        }
+        for (i, count) in counts.iter().enumerate() {
+            if game.snake.contains(&pos(i as u16 % 4, i as u16 / 4)) {
+                assert_eq!(*count, 0);
+            } else {
+                assert!((50..=52).contains(count), "cell {} spawned {} times", i, count);
+            }
+        }
+    }
+
+    #[test]
+    fn spawn_takes_last_free_cell() {
+        let mut game = Game::with_config(GameConfig::new(4, 1, 10), BoardMode::Walls, 5);
+        assert_eq!(game.snake, [pos(2, 0), pos(1, 0), pos(0, 0)]);
+        assert_eq!(game.food, pos(3, 0));
+
+        game.walls.set_wall(pos(3, 0), true);
+        assert!(!game.spawn_food());
+        assert_eq!(game.food, pos(3, 0));
+    }
+
+    #[test]
+    fn filling_the_board_wins() {
+        let mut game = started(Game::with_config(GameConfig::new(4, 1, 10), BoardMode::Walls, 5));
+        game.update();
+        assert_eq!(game.state, GameState::Cleared);
+        assert!(game.is_finished());
+        assert!(!game.is_game_over());
+        assert_eq!(game.score, 1);
+        assert_eq!(game.snake.len(), 4);
+
+        // 获胜后按键回到开始画面
+        game.press_button();
+        assert_eq!(game.state, GameState::Title);
+        assert_eq!(game.snake.len(), 3);
+    }
+
+
+    #[test]
+    fn moves_one_cell_per_update() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.update();
+        assert_eq!(game.snake, vec![pos(13, 12), pos(12, 12), pos(11, 12)]);
+
+        game.set_direction(Direction::Down);
+        game.update();
+        assert_eq!(game.snake, vec![pos(13, 13), pos(13, 12), pos(12, 12)]);
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn rejects_reverse_direction() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.set_direction(Direction::Left);
+        game.update();
+        assert_eq!(game.direction, Direction::Right);
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn direction_applies_on_update_only() {
+        let mut game = started(Game::with_seed(1));
+        game.set_direction(Direction::Up);
+        assert_eq!(game.direction, Direction::Right);
+        game.food = pos(0, 0);
+        game.update();
+        assert_eq!(game.direction, Direction::Up);
+    }
+
+    #[test]
+    fn double_turn_within_tick_cannot_reverse() {
+        // Right -> Up -> Left 在同一个 tick 内，不能直接掉头撞自己
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.set_direction(Direction::Up);
+        game.set_direction(Direction::Left);
+
+        game.update();
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(12, 11));
+
+        game.update();
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(11, 11));
+    }
+
+    #[test]
+    fn queued_reverse_of_queued_turn_is_rejected() {
+        // Right -> Up -> Down：Down 与 Up 相反，被忽略
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.set_direction(Direction::Up);
+        game.set_direction(Direction::Down);
+
+        game.update();
+        game.update();
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(12, 10));
+    }
+
+    #[test]
+    fn one_turn_per_update() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.set_direction(Direction::Down);
+        game.set_direction(Direction::Left);
+        game.set_direction(Direction::Up);
+
+        game.update();
+        assert_eq!(game.direction, Direction::Down);
+        game.update();
+        assert_eq!(game.direction, Direction::Left);
+        game.update();
+        assert_eq!(game.direction, Direction::Up);
+        assert_eq!(game.snake[0], pos(11, 12));
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn input_queue_is_bounded() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        for dir in [Direction::Up, Direction::Left, Direction::Down, Direction::Right, Direction::Up] {
+            game.set_direction(dir);
+        }
+        for _ in 0..INPUT_QUEUE_LEN {
+            game.update();
+        }
+        assert_eq!(game.direction, Direction::Down);
+
+        // 超出部分已被丢弃
+        game.update();
+        assert_eq!(game.direction, Direction::Down);
+    }
+
+    #[test]
+    fn repeated_direction_is_not_queued() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        for _ in 0..10 {
+            game.set_direction(Direction::Right);
+        }
+        assert!(game.pending.is_empty());
+
+        for _ in 0..10 {
+            game.set_direction(Direction::Down);
+        }
+        assert_eq!(game.pending.len(), 1);
+    }
+
+    #[test]
+    fn reset_clears_input_queue() {
+        let mut game = started(Game::with_seed(1));
+        game.set_direction(Direction::Up);
+        game.reset();
+        game.press_button();
+        game.food = pos(0, 0);
+        game.update();
+        assert_eq!(game.direction, Direction::Right);
+    }
+
+    #[test]
+    fn eating_food_grows_and_scores() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(13, 12);
+        game.update();
+        assert_eq!(game.score, 1);
+        assert_eq!(game.snake.len(), 4);
+        assert_eq!(game.snake[0], pos(13, 12));
+        assert_ne!(game.food, pos(13, 12));
+        assert!(!game.snake.contains(&game.food));
+    }
+
+    #[test]
+    fn hitting_right_wall_ends_game() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        for _ in 0..(COLS - 13) {
+            game.update();
+            assert!(!game.is_game_over());
+        }
+        assert_eq!(game.snake[0], pos(COLS - 1, 12));
+        game.update();
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn hitting_top_wall_ends_game() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.set_direction(Direction::Up);
+        for _ in 0..=12 {
+            game.update();
+        }
+        assert!(game.is_game_over());
+        assert_eq!(game.snake[0], pos(12, 0));
+    }
+
+    #[test]
+    fn hitting_self_ends_game() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.snake = body(&[pos(5, 5), pos(4, 5), pos(4, 6), pos(5, 6), pos(6, 6)]);
+        game.set_direction(Direction::Down);
+        game.update();
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn game_over_freezes_state() {
+        let mut game = started(Game::with_seed(1));
+        game.state = GameState::GameOver;
+        let before = game.snake.clone();
+        game.update();
+        assert_eq!(game.snake, before);
+    }
+
+    #[test]
+    fn reset_keeps_rng_stream() {
+        let mut game = started(Game::with_seed(9));
+        let mut reference = Lcg::new(9);
+        let first = nth_free((reference.next_u16() as usize * FREE) >> 16);
+        assert_eq!(game.food, first);
+
+        game.reset();
+        let second = nth_free((reference.next_u16() as usize * FREE) >> 16);
+        assert_eq!(game.food, second);
+        assert_eq!(game.score, 0);
+        assert_eq!(game.snake.len(), 3);
+    }
+
+    /// 把蛇放在边缘，朝外移动一步
+    fn edge_game(mode: BoardMode, head: Position, dir: Direction) -> Box<Game> {
+        let mut game = started(Game::with_mode(mode, 1));
+        let back = dir.opposite();
+        let mut cells = vec![head];
+        for _ in 0..2 {
+            let last = *cells.last().unwrap();
+            cells.push(game.step(last, back).unwrap());
+        }
+        game.snake = body(&cells);
+        game.direction = dir;
+        game.food = pos(COLS / 2, ROWS / 2);
+        game.update();
+        game
+    }
+
+    #[test]
+    fn walls_end_game_on_every_edge() {
+        for (head, dir) in [
+            (pos(5, 0), Direction::Up),
+            (pos(5, ROWS - 1), Direction::Down),
+            (pos(0, 5), Direction::Left),
+            (pos(COLS - 1, 5), Direction::Right),
+        ] {
+            let game = edge_game(BoardMode::Walls, head, dir);
+            assert!(game.is_game_over(), "{:?} at {:?}", dir, head);
+            assert_eq!(game.snake[0], head);
+        }
+    }
+
+    #[test]
+    fn wrap_through_top_edge() {
+        let game = edge_game(BoardMode::Wrap, pos(5, 0), Direction::Up);
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(5, ROWS - 1));
+    }
+
+    #[test]
+    fn wrap_through_bottom_edge() {
+        let game = edge_game(BoardMode::Wrap, pos(5, ROWS - 1), Direction::Down);
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(5, 0));
+    }
+
+    #[test]
+    fn wrap_through_left_edge() {
+        let game = edge_game(BoardMode::Wrap, pos(0, 5), Direction::Left);
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(COLS - 1, 5));
+    }
+
+    #[test]
+    fn wrap_through_right_edge() {
+        let game = edge_game(BoardMode::Wrap, pos(COLS - 1, 5), Direction::Right);
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(0, 5));
+        assert_eq!(game.snake[1], pos(COLS - 1, 5));
+    }
+
+    #[test]
+    fn wrap_still_detects_self_collision() {
+        let mut game = started(Game::with_mode(BoardMode::Wrap, 1));
+        game.food = pos(12, 12);
+        let last = COLS - 1;
+        game.snake = body(&[pos(last, 3), pos(last - 1, 3), pos(0, 3)]);
+        game.direction = Direction::Right;
+        game.update();
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn reset_keeps_board_mode() {
+        let mut game = Game::with_mode(BoardMode::Wrap, 3);
+        game.reset();
+        assert_eq!(game.mode, BoardMode::Wrap);
+    }
+
+    #[test]
+    fn panel_config_divides_pixels() {
+        assert_eq!(GameConfig::for_panel(320, 240, 10), GameConfig::new(32, 24, 10));
+        assert_eq!(GameConfig::for_panel(128, 128, 10), GameConfig::new(12, 12, 10));
+        assert_eq!(GameConfig::new(32, 24, 10).width_px(), 320);
+        assert_eq!(GameConfig::new(32, 24, 10).height_px(), 240);
+    }
+
+    #[test]
+    fn non_square_board_starts_centered_and_spawns_inside() {
+        let config = GameConfig::new(32, 8, 10);
+        for seed in 0..50 {
+            let game = Game::with_config(config, BoardMode::Walls, seed);
+            assert_eq!(game.snake[0], pos(16, 4));
+            assert!(game.food.x < 32 && game.food.y < 8);
+        }
+    }
+
+    #[test]
+    fn non_square_walls_use_own_axis() {
+        let mut game = started(Game::with_config(GameConfig::new(32, 8, 10), BoardMode::Walls, 1));
+        game.food = pos(0, 0);
+        game.set_direction(Direction::Down);
+        for _ in 0..3 {
+            game.update();
+        }
+        assert!(!game.is_game_over());
+        assert_eq!(game.snake[0], pos(16, 7));
+        game.update();
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn non_square_wrap_uses_own_axis() {
+        let mut game = started(Game::with_config(GameConfig::new(32, 8, 10), BoardMode::Wrap, 1));
+        game.food = pos(0, 5);
+        game.set_direction(Direction::Down);
+        for _ in 0..4 {
+            game.update();
+        }
+        assert_eq!(game.snake[0], pos(16, 0));
+
+        game.set_direction(Direction::Right);
+        for _ in 0..16 {
+            game.update();
+        }
+        assert_eq!(game.snake[0], pos(0, 0));
+        assert!(!game.is_game_over());
+    }
+
+    const TEST_LEVELS: [&str; 2] = [
+        "",
+        "#\n",
+    ];
+
+    #[test]
+    fn walls_end_game() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(0, 0);
+        game.walls.set_wall(pos(14, 12), true);
+        game.update();
+        assert!(!game.is_game_over());
+        game.update();
+        assert!(game.is_game_over());
+        assert_eq!(game.snake[0], pos(13, 12));
+    }
+
+    #[test]
+    fn food_never_spawns_on_walls() {
+        let mut game = Game::with_rng(
+            GameConfig::default(),
+            BoardMode::Walls,
+            Box::new(SeqRng { values: vec![0, 0, roll(2, FREE - 2)], index: 0 }),
+        );
+        game.walls.set_wall(pos(0, 0), true);
+        game.walls.set_wall(pos(3, 0), true);
+        game.spawn_food();
+        assert_eq!(game.food, pos(1, 0));
+        game.spawn_food();
+        assert_eq!(game.food, pos(4, 0));
+    }
+
+    #[test]
+    fn levels_load_walls_and_clear_start() {
+        static MAPS: [&str; 1] = ["...........................\n\n\n\n\n\n\n\n\n\n\n\n############################"];
+        let mut game = started(Game::with_seed(1));
+        game.set_levels(&MAPS);
+        assert_eq!(game.level, 0);
+        assert!(game.walls.is_wall(pos(0, 12)));
+        assert!(!game.walls.is_wall(pos(12, 12)));
+        assert!(!game.walls.is_wall(pos(10, 12)));
+        assert!(!game.walls.is_wall(game.food));
+    }
+
+    #[test]
+    fn advances_level_after_threshold() {
+        let mut game = started(Game::with_seed(1));
+        game.set_levels(&TEST_LEVELS);
+        for i in 0..LEVEL_UP_SCORE {
+            assert_eq!(game.level, 0);
+            let head = game.snake[0];
+            game.food = pos(head.x + 1, head.y);
+            game.update();
+            assert_eq!(game.score, i + 1);
+        }
+
+        // 进入下一关：障碍物更新，蛇回到起点，分数保留
+        assert_eq!(game.level, 1);
+        assert!(game.walls.is_wall(pos(0, 0)));
+        assert_eq!(game.snake, vec![pos(12, 12), pos(11, 12), pos(10, 12)]);
+        assert_eq!(game.direction, Direction::Right);
+        assert_eq!(game.score, LEVEL_UP_SCORE);
+    }
+
+    #[test]
+    fn last_level_does_not_advance() {
+        let mut game = started(Game::with_seed(1));
+        game.set_levels(&TEST_LEVELS[..1]);
+        for _ in 0..LEVEL_UP_SCORE + 1 {
+            let head = game.snake[0];
+            game.food = pos(head.x + 1, head.y);
+            game.update();
+        }
+        assert_eq!(game.level, 0);
+        assert_eq!(game.snake.len(), 3 + LEVEL_UP_SCORE as usize + 1);
+    }
+
+    #[test]
+    fn reset_returns_to_first_level() {
+        let mut game = started(Game::with_seed(1));
+        game.set_levels(&TEST_LEVELS);
+        game.load_level(1);
+        game.reset();
+        assert_eq!(game.level, 0);
+        assert_eq!(game.walls.walls().count(), 0);
+    }
+
+    #[test]
+    fn tick_interval_starts_at_base() {
+        assert_eq!(Difficulty::Easy.tick_interval_ms(0), 200);
+        assert_eq!(Difficulty::Normal.tick_interval_ms(0), 150);
+        assert_eq!(Difficulty::Hard.tick_interval_ms(0), 100);
+    }
+
+    #[test]
+    fn tick_interval_speeds_up_with_score() {
+        assert_eq!(Difficulty::Easy.tick_interval_ms(10), 180);
+        assert_eq!(Difficulty::Normal.tick_interval_ms(10), 120);
+        assert_eq!(Difficulty::Hard.tick_interval_ms(10), 60);
+
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            for score in 0..100 {
+                assert!(difficulty.tick_interval_ms(score + 1) <= difficulty.tick_interval_ms(score));
+            }
+        }
+    }
+
+    #[test]
+    fn tick_interval_is_clamped() {
+        assert_eq!(Difficulty::Easy.tick_interval_ms(50), 100);
+        assert_eq!(Difficulty::Normal.tick_interval_ms(27), 70);
+        assert_eq!(Difficulty::Hard.tick_interval_ms(u16::MAX), 50);
+    }
+
+    #[test]
+    fn harder_is_never_slower() {
+        for score in 0..200 {
+            let easy = Difficulty::Easy.tick_interval_ms(score);
+            let normal = Difficulty::Normal.tick_interval_ms(score);
+            let hard = Difficulty::Hard.tick_interval_ms(score);
+            assert!(easy >= normal && normal >= hard);
+        }
+    }
+
+    #[test]
+    fn game_tick_interval_follows_score_and_difficulty() {
+        let mut game = started(Game::with_seed(1));
+        assert_eq!(game.tick_interval_ms(), 150);
+        game.difficulty = Difficulty::Hard;
+        game.food = pos(13, 12);
+        game.update();
+        assert_eq!(game.tick_interval_ms(), 96);
+
+        game.reset();
+        assert_eq!(game.difficulty, Difficulty::Hard);
+        assert_eq!(game.tick_interval_ms(), 100);
+    }
+
+    #[test]
+    fn starts_on_title_screen() {
+        let mut game = Game::with_seed(1);
+        assert_eq!(game.state, GameState::Title);
+        game.set_direction(Direction::Up);
+        game.update();
+        assert_eq!(game.snake[0], pos(12, 12));
+        assert!(game.pending.is_empty());
+    }
+
+    #[test]
+    fn button_starts_pauses_and_resumes() {
+        let mut game = Game::with_seed(1);
+        game.food = pos(0, 0);
+        game.press_button();
+        assert_eq!(game.state, GameState::Playing);
+        game.update();
+        assert_eq!(game.snake[0], pos(13, 12));
+
+        game.press_button();
+        assert_eq!(game.state, GameState::Paused);
+        game.set_direction(Direction::Up);
+        game.update();
+        assert_eq!(game.snake[0], pos(13, 12));
+
+        game.press_button();
+        assert_eq!(game.state, GameState::Playing);
+        game.update();
+        assert_eq!(game.snake[0], pos(14, 12));
+    }
+
+    #[test]
+    fn collision_enters_game_over_and_keeps_score() {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(13, 12);
+        game.update();
+        game.walls.set_wall(pos(14, 12), true);
+        game.update();
+        assert_eq!(game.state, GameState::GameOver);
+        assert_eq!(game.score, 1);
+    }
+
+    #[test]
+    fn button_on_game_over_returns_to_title() {
+        let mut game = started(Game::with_seed(1));
+        game.score = 7;
+        game.state = GameState::GameOver;
+        game.press_button();
+        assert_eq!(game.state, GameState::Title);
+        assert_eq!(game.score, 0);
+        assert_eq!(game.snake.len(), 3);
+    }
+
+    #[test]
+    fn reset_with_seed_matches_fresh_game() {
+        let mut game = Game::with_seed(1);
+        game.set_levels(&TEST_LEVELS);
+        game.reset_with_seed(77);
+
+        let mut fresh = Game::with_seed(77);
+        fresh.set_levels(&TEST_LEVELS);
+        assert_eq!(game.food, fresh.food);
+        assert_eq!(game.state, GameState::Title);
+    }
+
+    /// 双人模式，已离开开始画面，食物放在角落
+    fn two_player() -> Box<Game> {
+        let mut game = Game::with_seed(1);
+        game.set_two_player(true);
+        game.press_button();
+        game.food = pos(0, 0);
+        game
+    }
+
+    fn p2(game: &mut Game) -> &mut Player {
+        game.player2.as_mut().unwrap()
+    }
+
+    #[test]
+    fn two_player_snakes_start_apart() {
+        let mut game = Game::with_seed(1);
+        game.set_two_player(true);
+        assert_eq!(game.snake, [pos(12, 8), pos(11, 8), pos(10, 8)]);
+        assert_eq!(p2(&mut game).snake, [pos(11, 15), pos(12, 15), pos(13, 15)]);
+        assert_eq!(p2(&mut game).direction, Direction::Left);
+        assert!(!game.is_occupied(game.food));
+
+        game.set_two_player(false);
+        assert!(game.player2.is_none());
+        assert_eq!(game.snake[0], pos(12, 12));
+    }
+
+    #[test]
+    fn players_steer_independently() {
+        let mut game = two_player();
+        game.set_direction(Direction::Up);
+        game.set_player2_direction(Direction::Down);
+        game.update();
+        assert_eq!(game.snake[0], pos(12, 7));
+        assert_eq!(p2(&mut game).snake[0], pos(11, 16));
+
+        // 第二条蛇也不能 180 度转向
+        game.set_player2_direction(Direction::Up);
+        game.update();
+        assert_eq!(p2(&mut game).snake[0], pos(11, 17));
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn player2_direction_ignored_in_single_player() {
+        let mut game = started(Game::with_seed(1));
+        game.set_player2_direction(Direction::Up);
+        game.update();
+        assert_eq!(game.direction, Direction::Right);
+    }
+
+    #[test]
+    fn player2_scores_separately() {
+        let mut game = two_player();
+        game.food = pos(10, 15);
+        game.update();
+        game.update();
+        assert_eq!(game.score, 0);
+        assert_eq!(p2(&mut game).score, 1);
+        assert_eq!(p2(&mut game).snake.len(), 4);
+        assert_eq!(game.snake.len(), 3);
+        assert!(!game.is_occupied(game.food));
+    }
+
+    #[test]
+    fn head_to_head_is_a_draw() {
+        let mut game = two_player();
+        game.snake = body(&[pos(5, 5), pos(4, 5), pos(3, 5)]);
+        p2(&mut game).snake = body(&[pos(7, 5), pos(8, 5), pos(9, 5)]);
+        game.update();
+        assert_eq!(game.state, GameState::GameOver);
+        assert_eq!(game.result, Some(MatchResult::Draw));
+    }
+
+    #[test]
+    fn swapping_heads_is_a_draw() {
+        let mut game = two_player();
+        game.snake = body(&[pos(5, 5), pos(4, 5), pos(3, 5)]);
+        p2(&mut game).snake = body(&[pos(6, 5), pos(7, 5), pos(8, 5)]);
+        game.update();
+        assert_eq!(game.result, Some(MatchResult::Draw));
+    }
+
+    #[test]
+    fn head_into_other_body_loses() {
+        let mut game = two_player();
+        game.snake = body(&[pos(5, 5), pos(4, 5), pos(3, 5)]);
+        let p = p2(&mut game);
+        p.snake = body(&[pos(6, 4), pos(6, 5), pos(6, 6)]);
+        p.direction = Direction::Up;
+        game.update();
+        assert_eq!(game.state, GameState::GameOver);
+        assert_eq!(game.result, Some(MatchResult::Player2Wins));
+    }
+
+    #[test]
+    fn player2_hitting_wall_loses() {
+        let mut game = two_player();
+        p2(&mut game).snake = body(&[pos(0, 15), pos(1, 15), pos(2, 15)]);
+        game.update();
+        assert_eq!(game.result, Some(MatchResult::Player1Wins));
+    }
+
+    #[test]
+    fn reset_keeps_two_player_mode() {
+        let mut game = two_player();
+        game.set_player2_direction(Direction::Up);
+        game.update();
+        p2(&mut game).score = 3;
+        game.state = GameState::GameOver;
+        game.result = Some(MatchResult::Player1Wins);
+        game.press_button();
+
+        assert!(game.is_two_player());
+        assert_eq!(game.result, None);
+        assert_eq!(p2(&mut game).score, 0);
+        assert_eq!(p2(&mut game).snake[0], pos(11, 15));
+    }
+
+    #[test]
+    fn two_player_levels_count_both_scores() {
+        let mut game = Game::with_seed(1);
+        game.set_two_player(true);
+        game.set_levels(&TEST_LEVELS);
+        game.press_button();
+        game.score = 2;
+        p2(&mut game).score = LEVEL_UP_SCORE - 3;
+        game.food = pos(13, 8);
+        game.update();
+        assert_eq!(game.level, 1);
+        assert_eq!(game.snake[0], pos(12, 8));
+    }
+
+    #[test]
+    fn levels_advance_by_score_not_food_count() {
+        // 奖励食物按分数计入关卡进度
+        for (score, level) in [(LEVEL_UP_SCORE - food::BONUS_POINTS - 1, 0), (LEVEL_UP_SCORE - food::BONUS_POINTS, 1)] {
+            let mut game = started(Game::with_seed(1));
+            game.set_levels(&TEST_LEVELS);
+            game.score = score;
+            game.food = pos(13, 12);
+            game.food_kind = FoodKind::Bonus;
+            game.update();
+            assert_eq!(game.level, level, "score {score}");
+        }
+    }
+
+    /// 蛇头在 (12, 12) 向右，下一步吃到指定种类的食物
+    fn food_ahead(kind: FoodKind) -> Box<Game> {
+        let mut game = started(Game::with_seed(1));
+        game.food = pos(13, 12);
+        game.food_kind = kind;
+        game.food_ticks = kind.lifetime();
+        game
+    }
+
+    #[test]
+    fn bonus_food_scores_more() {
+        let mut game = food_ahead(FoodKind::Bonus);
+        game.update();
+        assert_eq!(game.score, food::BONUS_POINTS);
+        assert_eq!(game.snake.len(), 4);
+    }
+
+    #[test]
+    fn shrink_food_removes_segments() {
+        let mut game = food_ahead(FoodKind::Shrink);
+        game.snake = body(&[pos(12, 12), pos(11, 12), pos(10, 12), pos(9, 12), pos(8, 12), pos(7, 12)]);
+        game.update();
+        assert_eq!(game.score, 1);
+        assert_eq!(game.snake, [pos(13, 12), pos(12, 12), pos(11, 12), pos(10, 12)]);
+    }
+
+    #[test]
+    fn shrink_food_keeps_minimum_length() {
+        let mut game = food_ahead(FoodKind::Shrink);
+        game.update();
+        assert_eq!(game.snake.len(), MIN_LEN);
+        assert_eq!(game.snake[0], pos(13, 12));
+    }
+
+    #[test]
+    fn speed_up_food_shortens_interval_for_a_while() {
+        let mut game = food_ahead(FoodKind::SpeedUp);
+        game.update();
+        assert_eq!(game.snake.len(), 4);
+        assert_eq!(game.boost_ticks, SPEED_UP_TICKS);
+        assert_eq!(game.tick_interval_ms(), 147 * SPEED_UP_PERCENT / 100);
+
+        // 穿墙模式下一直向上走，不会撞墙
+        game.mode = BoardMode::Wrap;
+        game.food = pos(0, 0);
+        game.food_kind = FoodKind::Normal;
+        game.set_direction(Direction::Up);
+        for _ in 0..SPEED_UP_TICKS {
+            game.update();
+        }
+        assert_eq!(game.state, GameState::Playing);
+        assert_eq!(game.boost_ticks, 0);
+        assert_eq!(game.tick_interval_ms(), 147);
+    }
+
+    #[test]
+    fn timed_food_expires_and_respawns() {
+        let mut game = food_ahead(FoodKind::Bonus);
+        game.food = pos(0, 0);
+        game.food_ticks = Some(2);
+        game.update();
+        assert_eq!(game.food_ticks, Some(1));
+        assert_eq!(game.food, pos(0, 0));
+
+        game.update();
+        // 默认概率表只有普通食物
+        assert_eq!(game.food_kind, FoodKind::Normal);
+        assert_eq!(game.food_ticks, None);
+        assert_eq!(game.score, 0);
+    }
+
+    #[test]
+    fn normal_food_never_expires() {
+        let mut game = food_ahead(FoodKind::Normal);
+        game.food = pos(12, 0);
+        for _ in 0..10 {
+            game.update();
+        }
+        assert_eq!(game.food, pos(12, 0));
+    }
+
+    #[test]
+    fn food_table_produces_every_kind() {
+        let mut seen = Vec::new();
+        for seed in 0..200 {
+            let mut game = Game::with_seed(seed);
+            game.set_food_table(&food::FOOD_TABLE);
+            game.set_levels(&TEST_LEVELS);
+            assert_eq!(game.food_ticks, game.food_kind.lifetime());
+            if !seen.contains(&game.food_kind) {
+                seen.push(game.food_kind);
+            }
+        }
+        assert_eq!(seen.len(), food::FOOD_TABLE.len());
+    }
+
+    #[test]
+    fn reset_keeps_food_table() {
+        let mut game = Game::with_seed(3);
+        game.set_food_table(&food::FOOD_TABLE);
+        game.set_levels(&TEST_LEVELS);
+        game.reset_with_seed(9);
+
+        let mut fresh = Game::with_seed(9);
+        fresh.set_food_table(&food::FOOD_TABLE);
+        fresh.set_levels(&TEST_LEVELS);
+        assert_eq!((game.food, game.food_kind), (fresh.food, fresh.food_kind));
+    }
+
+    #[test]
+    fn player2_gets_food_effect() {
+        let mut game = two_player();
+        game.food = pos(10, 15);
+        game.food_kind = FoodKind::Bonus;
+        game.update();
+        game.update();
+        assert_eq!(p2(&mut game).score, food::BONUS_POINTS);
+        assert_eq!(game.score, 0);
+    }
 }
@@ -0,0 +1,491 @@
+//! 图块绘制模块
+//!
+//! 用嵌入的图集 `sprites.tga`（由 `make_sprites.py` 生成）代替纯色方块绘制棋盘：
+//! 蛇头和蛇尾按方向选择图块，中间的蛇身按前后两节所在的方向选择直线或拐角。
+//! 分数用 BDF 字体显示在棋盘右上角。
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use embedded_graphics::{
+    Drawable,
+    geometry::Dimensions,
+    image::{Image, ImageDrawableExt},
+    pixelcolor::Rgb565,
+    prelude::{DrawTarget, Point, Primitive, RgbColor, Size},
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Baseline, Text, renderer::TextRenderer},
+};
+use tinytga::Tga;
+
+use crate::bdf::{BdfTextStyle, regular_font::REGULAR_FONT};
+use crate::body::Body;
+use crate::food::FoodKind;
+use crate::render::{
+    BODY_COLOR, CellCache, PLAYER2_BODY_COLOR, PLAYER2_HEAD_COLOR, WALL_COLOR, cell_rect, draw_overlay,
+    food_color, restore_border,
+};
+use crate::snake::{Direction, Game, GameState, Position};
+
+/// 图块边长（像素），格子大小不同时退回纯色方块
+pub const TILE_PX: u16 = 10;
+
+/// 分数颜色
+pub const SCORE_COLOR: Rgb565 = Rgb565::WHITE;
+
+/// 图集：每行 14 个图块，第 0、1 行为两条蛇，第 2 行为食物和障碍物
+static ATLAS: &[u8] = include_bytes!("../sprites.tga");
+
+/// 蛇的一节
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Segment {
+    /// 蛇头（前进方向）
+    Head(Direction),
+    /// 蛇尾（指向前一节的方向）
+    Tail(Direction),
+    /// 左右相连
+    Horizontal,
+    /// 上下相连
+    Vertical,
+    /// 拐角：连接上方和左侧
+    UpLeft,
+    /// 拐角：连接上方和右侧
+    UpRight,
+    /// 拐角：连接下方和左侧
+    DownLeft,
+    /// 拐角：连接下方和右侧
+    DownRight,
+}
+
+impl Segment {
+    /// 由前后两节所在的方向得到中间一节
+    fn joining(a: Direction, b: Direction) -> Self {
+        use Direction::*;
+        match (a, b) {
+            (Up, Down) | (Down, Up) => Segment::Vertical,
+            (Up, Left) | (Left, Up) => Segment::UpLeft,
+            (Up, Right) | (Right, Up) => Segment::UpRight,
+            (Down, Left) | (Left, Down) => Segment::DownLeft,
+            (Down, Right) | (Right, Down) => Segment::DownRight,
+            _ => Segment::Horizontal,
+        }
+    }
+
+    /// 在图集一行中的列号
+    fn column(self) -> u32 {
+        let dir = |dir: Direction| match dir {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        };
+        match self {
+            Segment::Head(d) => dir(d),
+            Segment::Tail(d) => 4 + dir(d),
+            Segment::Horizontal => 8,
+            Segment::Vertical => 9,
+            Segment::UpLeft => 10,
+            Segment::UpRight => 11,
+            Segment::DownLeft => 12,
+            Segment::DownRight => 13,
+        }
+    }
+}
+
+/// 格子上的图块
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Tile {
+    Empty,
+    Wall,
+    /// 第一条蛇
+    Snake1(Segment),
+    /// 第二条蛇（双人模式）
+    Snake2(Segment),
+    Food(FoodKind),
+}
+
+impl Tile {
+    /// 图块在图集中的位置（列，行），空格子没有图块
+    fn atlas_cell(self) -> Option<(u32, u32)> {
+        match self {
+            Tile::Empty => None,
+            Tile::Snake1(segment) => Some((segment.column(), 0)),
+            Tile::Snake2(segment) => Some((segment.column(), 1)),
+            Tile::Food(kind) => Some((kind as u32, 2)),
+            Tile::Wall => Some((4, 2)),
+        }
+    }
+
+    /// 格子大小与图块不同时使用的颜色（与 `draw_game` 相同）
+    fn color(self) -> Rgb565 {
+        match self {
+            Tile::Empty => Rgb565::BLACK,
+            Tile::Wall => WALL_COLOR,
+            Tile::Snake1(Segment::Head(_)) => Rgb565::GREEN,
+            Tile::Snake1(_) => BODY_COLOR,
+            Tile::Snake2(Segment::Head(_)) => PLAYER2_HEAD_COLOR,
+            Tile::Snake2(_) => PLAYER2_BODY_COLOR,
+            Tile::Food(kind) => food_color(kind),
+        }
+    }
+}
+
+/// 第 `i` 节（蛇头为第 0 节）的图块，`heading` 为只有蛇头一节时的朝向
+pub fn segment(game: &Game, body: &Body, i: usize, heading: Direction) -> Segment {
+    let front = if i > 0 { game.direction_between(body[i], body[i - 1]) } else { None };
+    let back = if i + 1 < body.len() { game.direction_between(body[i], body[i + 1]) } else { None };
+
+    if i == 0 {
+        Segment::Head(back.map_or(heading, |dir| dir.opposite()))
+    } else if i + 1 == body.len() {
+        Segment::Tail(front.unwrap_or(heading))
+    } else {
+        match (front, back) {
+            (Some(a), Some(b)) => Segment::joining(a, b),
+            _ => Segment::Horizontal,
+        }
+    }
+}
+
+/// 当前帧每个格子的图块（按行排列）
+pub fn tile_map(game: &Game) -> Vec<Tile> {
+    let cols = game.config.cols as usize;
+    let mut tiles = vec![Tile::Empty; cols * game.config.rows as usize];
+    let mut set = |pos: Position, tile: Tile| {
+        if let Some(slot) = tiles.get_mut(pos.y as usize * cols + pos.x as usize) {
+            *slot = tile;
+        }
+    };
+
+    for pos in game.walls.walls() {
+        set(pos, Tile::Wall);
+    }
+    for (i, pos) in game.snake.iter().enumerate() {
+        set(*pos, Tile::Snake1(segment(game, &game.snake, i, game.direction)));
+    }
+    if let Some(p2) = &game.player2 {
+        for (i, pos) in p2.snake.iter().enumerate() {
+            set(*pos, Tile::Snake2(segment(game, &p2.snake, i, p2.direction)));
+        }
+    }
+    set(game.food, Tile::Food(game.food_kind));
+    tiles
+}
+
+/// 右上角显示的分数，双人模式为 `P1:P2`
+fn score_text(game: &Game) -> String {
+    match &game.player2 {
+        Some(p2) => alloc::format!("{}:{}", game.score, p2.score),
+        None => alloc::format!("{}", game.score),
+    }
+}
+
+/// 右上角的分数文字（不绘制背景，直接叠加在图块上）
+///
+/// `BdfTextStyle` 的 `measure_string` 把基线偏移也算进了 `next_position`，
+/// 不能用 `Alignment::Right`，这里先量出宽度再左对齐
+fn score_label<'a>(game: &Game, text: &'a str) -> Text<'a, BdfTextStyle<'static, Rgb565>> {
+    let style = BdfTextStyle::new(&REGULAR_FONT, SCORE_COLOR);
+    let width = style.measure_string(text, Point::zero(), Baseline::Top).next_position.x;
+    Text::with_baseline(
+        text,
+        Point::new(game.config.width_px() as i32 - 3 - width, 2),
+        style,
+        Baseline::Top,
+    )
+}
+
+/// 图块绘制器
+///
+/// 与 `DirtyRenderer` 用同样的逐格比较，只重绘变化的格子。蛇移动时除了新蛇头
+/// 和离开的蛇尾，旧蛇头变成直线或拐角、新蛇尾换成蛇尾图块，也都会重绘。
+/// 分数变化时先重绘旧分数下面的格子，再画新分数。
+pub struct SpriteRenderer {
+    atlas: Tga<'static, Rgb565>,
+    tiles: CellCache<Tile>,
+    score: String,
+}
+
+impl Default for SpriteRenderer {
+    fn default() -> Self {
+        Self {
+            atlas: Tga::from_slice(ATLAS).unwrap(),
+            tiles: CellCache::default(),
+            score: String::new(),
+        }
+    }
+}
+
+impl SpriteRenderer {
+    /// 创建绘制器，第一次绘制为整屏重绘
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 下次绘制时整屏重绘（例如屏幕被其他内容覆盖后）
+    pub fn invalidate(&mut self) {
+        self.tiles.invalidate();
+    }
+
+    /// 绘制一帧，返回是否进行了整屏重绘
+    ///
+    /// 非游戏状态下画面不变，只在进入该状态时绘制一次
+    pub fn draw<D>(&mut self, target: &mut D, game: &Game) -> Result<bool, D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let score = score_text(game);
+
+        let Some(mut changed) = self.tiles.update(game, tile_map(game)) else {
+            let board = Rectangle::new(
+                Point::zero(),
+                Size::new(game.config.width_px(), game.config.height_px()),
+            );
+            target.fill_solid(&board, Rgb565::BLACK)?;
+            for (pos, tile) in self.tiles.iter(game) {
+                if tile != Tile::Empty {
+                    self.draw_tile(target, game, pos, tile)?;
+                }
+            }
+            board.into_styled(PrimitiveStyle::with_stroke(Rgb565::WHITE, 1)).draw(target)?;
+            score_label(game, &score).draw(target)?;
+            draw_overlay(target, game)?;
+
+            self.score = score;
+            return Ok(true);
+        };
+
+        if game.state == GameState::Playing {
+            let old_area = score_label(game, &self.score).bounding_box();
+            let new_area = score_label(game, &score).bounding_box();
+            let score_changed = score != self.score;
+            let mut redraw_score = score_changed;
+
+            // 旧分数下面的格子也要重绘
+            if score_changed {
+                for (pos, _) in self.tiles.iter(game) {
+                    if overlaps(&cell_rect(game, pos), &old_area) && !changed.contains(&pos) {
+                        changed.push(pos);
+                    }
+                }
+            }
+            for pos in changed {
+                self.draw_tile(target, game, pos, self.tiles.get(game, pos))?;
+                restore_border(target, game, pos)?;
+                redraw_score |= overlaps(&cell_rect(game, pos), &new_area);
+            }
+            if redraw_score {
+                score_label(game, &score).draw(target)?;
+            }
+
+            self.score = score;
+        }
+
+        Ok(false)
+    }
+
+    /// 绘制一个格子的图块
+    fn draw_tile<D>(&self, target: &mut D, game: &Game, pos: Position, tile: Tile) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let rect = cell_rect(game, pos);
+        match tile.atlas_cell() {
+            Some((col, row)) if game.config.cell_px == TILE_PX => {
+                let source = Rectangle::new(
+                    Point::new((col * TILE_PX as u32) as i32, (row * TILE_PX as u32) as i32),
+                    Size::new_equal(TILE_PX as u32),
+                );
+                Image::new(&self.atlas.sub_image(&source), rect.top_left).draw(target)?;
+            }
+            _ => target.fill_solid(&rect, tile.color())?,
+        }
+        Ok(())
+    }
+}
+
+fn overlaps(a: &Rectangle, b: &Rectangle) -> bool {
+    !a.intersection(b).is_zero_sized()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snake::{BoardMode, GameConfig};
+    use alloc::boxed::Box;
+    use embedded_graphics::{mock_display::MockDisplay, prelude::PointsIter};
+
+    fn pos(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    fn segments(game: &Game) -> Vec<Segment> {
+        (0..game.snake.len()).map(|i| segment(game, &game.snake, i, game.direction)).collect()
+    }
+
+    /// 6x4 格子、每格 10 像素的棋盘（60x40 像素，MockDisplay 放得下）
+    fn small_game() -> Box<Game> {
+        let mut game = Game::with_config(GameConfig::new(6, 4, 10), BoardMode::Walls, 1);
+        game.press_button();
+        game.food = pos(0, 3);
+        game
+    }
+
+    fn display() -> MockDisplay<Rgb565> {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        display
+    }
+
+    fn full_redraw(game: &Game) -> MockDisplay<Rgb565> {
+        let mut display = display();
+        SpriteRenderer::new().draw(&mut display, game).unwrap();
+        display
+    }
+
+    #[test]
+    fn atlas_has_all_tiles() {
+        let atlas: Tga<Rgb565> = Tga::from_slice(ATLAS).unwrap();
+        assert_eq!(atlas.bounding_box().size, Size::new(14 * TILE_PX as u32, 3 * TILE_PX as u32));
+    }
+
+    #[test]
+    fn straight_snake_has_head_body_and_tail() {
+        let mut game = Game::with_seed(1);
+        game.press_button();
+        assert_eq!(segments(&game), [
+            Segment::Head(Direction::Right),
+            Segment::Horizontal,
+            Segment::Tail(Direction::Right),
+        ]);
+    }
+
+    #[test]
+    fn corners_follow_neighbors() {
+        let mut game = Game::with_seed(1);
+        game.press_button();
+        //   . B B .
+        //   . T H .
+        // 蛇头 (5,5) 向下，尾巴 (4,5) 向上
+        game.snake = Body::from_cells(&game.config, [pos(5, 5), pos(5, 4), pos(4, 4), pos(4, 5)]);
+        assert_eq!(segments(&game), [
+            Segment::Head(Direction::Down),
+            Segment::DownLeft,
+            Segment::DownRight,
+            Segment::Tail(Direction::Up),
+        ]);
+
+        game.snake = Body::from_cells(&game.config, [pos(5, 2), pos(4, 2), pos(4, 3), pos(4, 4), pos(5, 4), pos(5, 5)]);
+        assert_eq!(segments(&game), [
+            Segment::Head(Direction::Right),
+            Segment::DownRight,
+            Segment::Vertical,
+            Segment::UpRight,
+            Segment::DownLeft,
+            Segment::Tail(Direction::Up),
+        ]);
+
+        game.snake = Body::from_cells(&game.config, [pos(4, 2), pos(4, 3), pos(3, 3)]);
+        assert_eq!(segments(&game), [
+            Segment::Head(Direction::Up),
+            Segment::UpLeft,
+            Segment::Tail(Direction::Right),
+        ]);
+    }
+
+    #[test]
+    fn wrapped_snake_stays_connected() {
+        let mut game = Game::with_mode(BoardMode::Wrap, 1);
+        game.press_button();
+        game.snake = Body::from_cells(&game.config, [pos(0, 5), pos(23, 5), pos(22, 5)]);
+        assert_eq!(segments(&game), [
+            Segment::Head(Direction::Right),
+            Segment::Horizontal,
+            Segment::Tail(Direction::Right),
+        ]);
+    }
+
+    #[test]
+    fn draws_tiles_from_atlas() {
+        let mut game = small_game();
+        game.walls.set_wall(pos(0, 0), true);
+        let display = full_redraw(&game);
+
+        // 蛇头在 (3,2)，向右
+        assert_eq!(display.get_pixel(Point::new(30 + 2, 20 + 4)), Some(Rgb565::GREEN));
+        assert_eq!(display.get_pixel(Point::new(30 + 6, 20 + 3)), Some(Rgb565::WHITE));
+        // 蛇身 (2,2)：中间是蛇身颜色，上下边缘是黑色
+        assert_eq!(display.get_pixel(Point::new(20 + 4, 20 + 4)), Some(BODY_COLOR));
+        assert_eq!(display.get_pixel(Point::new(20 + 4, 20 + 1)), Some(Rgb565::BLACK));
+        // 食物 (0,3)
+        assert_eq!(display.get_pixel(Point::new(4, 30 + 4)), Some(Rgb565::RED));
+        // 障碍物 (0,0)，左上角被边框覆盖
+        assert_eq!(display.get_pixel(Point::new(2, 2)), Some(WALL_COLOR));
+        assert_eq!(display.get_pixel(Point::zero()), Some(Rgb565::WHITE));
+    }
+
+    #[test]
+    fn score_is_drawn_top_right() {
+        let mut game = small_game();
+        game.score = 7;
+        let display = full_redraw(&game);
+        let score_area = Rectangle::new(Point::new(30, 0), Size::new(30, 20));
+        let white = score_area.points().filter(|p| display.get_pixel(*p) == Some(SCORE_COLOR)).count();
+        // 边框之外还有数字的笔画
+        assert!(white > 30 + 20, "{} white pixels", white);
+    }
+
+    #[test]
+    fn other_cell_sizes_fall_back_to_colors() {
+        let mut game = Game::with_config(GameConfig::new(16, 16, 4), BoardMode::Walls, 1);
+        game.press_button();
+        let display = full_redraw(&game);
+        let head = game.snake[0];
+        let point = Point::new(head.x as i32 * 4 + 1, head.y as i32 * 4 + 1);
+        assert_eq!(display.get_pixel(point), Some(Rgb565::GREEN));
+    }
+
+    #[test]
+    fn move_redraws_four_cells() {
+        let mut game = small_game();
+        let mut renderer = SpriteRenderer::new();
+        renderer.draw(&mut display(), &game).unwrap();
+
+        game.update();
+        let mut changes = display();
+        assert!(!renderer.draw(&mut changes, &game).unwrap());
+
+        // 新蛇头 (4,2)、旧蛇头变蛇身、新蛇尾、离开的蛇尾 (1,2)
+        let drawn = changes.bounding_box().points().filter(|p| changes.get_pixel(*p).is_some()).count();
+        assert_eq!(drawn, 4 * 10 * 10);
+        assert_eq!(changes.affected_area(), Rectangle::new(Point::new(10, 20), Size::new(40, 10)));
+    }
+
+    #[test]
+    fn incremental_frames_match_full_redraw() {
+        let mut game = small_game();
+        let mut renderer = SpriteRenderer::new();
+        let mut screen = display();
+        renderer.draw(&mut screen, &game).unwrap();
+
+        // 转弯、经过分数下方、在棋盘边缘吃到食物（分数变化）之后，
+        // 增量绘制的结果与整屏重绘相同
+        game.food = pos(5, 1);
+        game.set_direction(Direction::Up);
+        game.update();
+        renderer.draw(&mut screen, &game).unwrap();
+        assert_eq!(screen, full_redraw(&game));
+
+        game.set_direction(Direction::Right);
+        game.update();
+        assert_eq!(game.score, 0);
+        renderer.draw(&mut screen, &game).unwrap();
+        assert_eq!(screen, full_redraw(&game));
+
+        game.update();
+        assert_eq!(game.score, 1);
+        renderer.draw(&mut screen, &game).unwrap();
+        assert_eq!(screen, full_redraw(&game));
+    }
+}